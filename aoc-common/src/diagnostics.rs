@@ -0,0 +1,108 @@
+//! Extra output from a solution that isn't its answer, such as the state of a
+//! simulation or how the answer was arrived at.
+//!
+//! Solutions log these with the `tracing` macros, at `debug!` for things worth
+//! a look and `trace!` for anything per step, rather than printing them. The
+//! runner decides which levels are shown, and with [`CaptureLayer`] installed
+//! it can [`capture`] them instead so they don't get mixed up with
+//! machine-readable output.
+
+use std::{cell::RefCell, fmt};
+
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::layer::{Context, Layer};
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Whether events on this thread are being captured, so shouldn't be
+/// written out as well.
+pub fn capturing() -> bool {
+    CAPTURED.with(|captured| captured.borrow().is_some())
+}
+
+/// Run `f`, returning what it returned along with every event it logged on
+/// this thread, as long as [`CaptureLayer`] is installed.
+pub fn capture<T, F: FnOnce() -> T>(f: F) -> (T, Vec<String>) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(vec![])));
+    let res = f();
+    let lines = CAPTURED.with(|captured| captured.replace(outer));
+    (res, lines.unwrap_or_default())
+}
+
+// Writes an event as its message followed by its fields, e.g.
+// "Drawing cursor_x=3 sprite_x=4"
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields += &format!(" {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = String::from(value);
+        } else {
+            self.fields += &format!(" {}={}", field.name(), value);
+        }
+    }
+}
+
+/// A layer that saves each event that gets through the filters while
+/// [`capture`] is running on the same thread.
+pub struct CaptureLayer;
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        CAPTURED.with(|captured| {
+            if let Some(lines) = captured.borrow_mut().as_mut() {
+                let mut line = Line::default();
+                event.record(&mut line);
+                lines.push(line.message + &line.fields);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::{debug, trace};
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn captures_events_with_their_fields() {
+        let subscriber = tracing_subscriber::registry().with(CaptureLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            trace!("Not captured");
+            let (answer, lines) = capture(|| {
+                assert!(capturing());
+                debug!(cursor_x = 3, sprite_x = 4, "Drawing");
+                trace!(stacks = "[A] [B]", "Final stacks are:\n{}", "AB");
+                42
+            });
+            assert!(!capturing());
+            assert_eq!(answer, 42);
+            assert_eq!(
+                lines,
+                [
+                    "Drawing cursor_x=3 sprite_x=4",
+                    "Final stacks are:\nAB stacks=[A] [B]"
+                ]
+            );
+        });
+    }
+}
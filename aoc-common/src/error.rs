@@ -0,0 +1,195 @@
+//! Errors shared between the days.
+//!
+//! A [`ParseError`] points at the bit of the input that couldn't be parsed,
+//! and renders with the offending line and a caret underneath it:
+//!
+//! ```text
+//! day9/src/input.txt:3:1: Could not parse direction token 'X'
+//!   |
+//! 3 | X 4
+//!   | ^
+//! ```
+//!
+//! Errors are created from string slices of the input, so the position can be
+//! worked out from where the slice sits in the input rather than having to
+//! count lines and columns by hand.
+
+use std::{error::Error, fmt, path::Path, str::FromStr};
+
+/// Part of the input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The file the input came from, if known. Solutions only see the input
+    /// text, so this is filled in by whoever read the file.
+    pub file: Option<String>,
+    /// Line number of the bad text, starting from 1
+    pub line: usize,
+    /// Column of the first bad character, starting from 1
+    pub column: usize,
+    /// The whole line containing the bad text
+    pub text: String,
+    /// How many characters of the line are bad. Can be 0 if something is
+    /// missing, in which case the caret points at where it should have been.
+    pub width: usize,
+    pub message: String,
+    // Byte offset and length of the bad text in the input it was found in,
+    // so the error can be moved if that input was itself part of a larger one.
+    offset: usize,
+    len: usize,
+}
+
+// Where `inner` starts in `outer`, in bytes. `inner` should be a slice of
+// `outer`. If it's not we can't say where it is, so just point at the start.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    let start = outer.as_ptr() as usize;
+    let pos = inner.as_ptr() as usize;
+    if pos < start || pos + inner.len() > start + outer.len() {
+        debug_assert!(false, "'{}' is not part of the input", inner);
+        return 0;
+    }
+    pos - start
+}
+
+impl ParseError {
+    /// An error about `bad`, which must be a slice of `input`. `input` can be
+    /// the whole puzzle input or any part of it, see [`ParseError::within`].
+    pub fn new<M: Into<String>>(input: &str, bad: &str, message: M) -> Self {
+        Self::locate(input, offset_in(input, bad), bad.len(), message.into())
+    }
+
+    /// Move an error found while parsing `inner` so that it's relative to
+    /// `outer`, which `inner` is a slice of. Useful for errors from `FromStr`
+    /// impls, which only see the bit of the input they're parsing.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        Self::locate(
+            outer,
+            offset_in(outer, inner) + self.offset,
+            self.len,
+            self.message,
+        )
+    }
+
+    /// Say which file the input came from.
+    pub fn in_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.file = Some(path.as_ref().display().to_string());
+        self
+    }
+
+    fn locate(input: &str, offset: usize, len: usize, message: String) -> Self {
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        // Don't let the caret run past the end of the line
+        let bad_end = (offset + len).min(line_end);
+        Self {
+            file: None,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: String::from(input[line_start..line_end].trim_end_matches('\r')),
+            width: input[offset..bad_end].chars().count(),
+            message,
+            offset,
+            len,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+        writeln!(f, "{}", self.message)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width.max(1))
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Fill in the file for `err` if it's a [`ParseError`], for whoever read the
+/// input from `path`.
+pub fn in_file<P: AsRef<Path>>(err: Box<dyn Error>, path: P) -> Box<dyn Error> {
+    match err.downcast::<ParseError>() {
+        Ok(err) => Box::new(err.in_file(path)),
+        Err(err) => err,
+    }
+}
+
+/// Parse `s`, a slice of `input`, as a `T`. Saves having to map the error from
+/// `str::parse` by hand every time we read a number.
+pub fn parse_in<T>(input: &str, s: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    s.parse()
+        .map_err(|err| ParseError::new(input, s, format!("'{}': {}", s, err)))
+}
+
+/// Parse `s`, a slice of `input`, with a `FromStr` impl that reports its own
+/// [`ParseError`]s, moving any error to where `s` is in `input`.
+pub fn parse_within<T>(input: &str, s: &str) -> Result<T, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    s.parse().map_err(|err: ParseError| err.within(input, s))
+}
+
+/// The input was understood, but doesn't have an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoSolutionError(pub String);
+
+impl fmt::Display for NoSolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No solution: {}", self.0)
+    }
+}
+
+impl Error for NoSolutionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_bad_text() {
+        let input = "R 4\nX 12\nU 3";
+        let err = ParseError::new(input, &input[6..8], "bad");
+        assert_eq!((err.line, err.column, err.width), (2, 3, 2));
+        assert_eq!(err.text, "X 12");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: bad\n  |\n2 | X 12\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn within_moves_error() {
+        let input = "R 4\nX 12\nU 3";
+        let line = &input[4..8];
+        let err = ParseError::new(line, &line[..1], "bad").within(input, line);
+        assert_eq!((err.line, err.column, err.width), (2, 1, 1));
+        assert_eq!(err.text, "X 12");
+    }
+
+    #[test]
+    fn missing_text_points_at_end() {
+        let input = "move 1 from";
+        let err = ParseError::new(input, &input[input.len()..], "missing").in_file("x.txt");
+        assert_eq!((err.line, err.column, err.width), (1, 12, 0));
+        assert!(err.to_string().starts_with("x.txt:1:12: missing"));
+        assert!(err.to_string().ends_with("|            ^"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Points and directions on a 2D plane.
+//!
+//! There are two ways round the y axis can go, and puzzles use both:
+//!
+//! - **screen** axes have y increasing *down* the page, the way a puzzle
+//!   input or a [`Grid`](crate::Grid) is laid out, so up is `(0, -1)`;
+//! - **math** axes have y increasing *up* the page, so up is `(0, 1)`.
+//!
+//! A [`Direction`] doesn't pick one. It has to be turned into a point with
+//! either [`Direction::screen`] or [`Direction::math`], so the choice is
+//! always written down where it's made. Rotating a direction doesn't depend
+//! on the axes, as clockwise is the same way round in both.
+
+use std::{
+    cmp, fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A point, or the offset between two points.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The integer point used for coordinates and for the dimensions of a
+/// [`Grid`](crate::Grid).
+pub type Vec2 = Point<i32>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Default> Point<T> {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+}
+
+// The distance between two numbers, without going negative for unsigned ones
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Distance when moving only along the axes
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Distance when diagonal moves are allowed too, like a king in chess
+    pub fn chebyshev(self, other: Self) -> T {
+        cmp::max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+}
+
+impl Point<i32> {
+    /// The sign of each coordinate, so a step of at most one in each axis
+    /// towards `self` from the origin.
+    pub fn signum(self) -> Self {
+        Self {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", &self.x, &self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+/// One of the four directions along the axes, in clockwise order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn rotate_cw(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        self.rotate_cw().opposite()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate_cw().rotate_cw()
+    }
+
+    /// A step in this direction on screen axes, where y increases down.
+    pub const fn screen(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// A step in this direction on math axes, where y increases up.
+    pub const fn math(self) -> Vec2 {
+        let step = self.screen();
+        Vec2::new(step.x, -step.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let a = Vec2::new(3, -4);
+        let b = Vec2::new(-1, 2);
+        assert_eq!(a + b, Vec2::new(2, -2));
+        assert_eq!(a - b, Vec2::new(4, -6));
+        assert_eq!(a * 3, Vec2::new(9, -12));
+        assert_eq!(-a, Vec2::new(-3, 4));
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(a.to_string(), "(3,-4)");
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec2::new(3, -4);
+        assert_eq!(a.manhattan(Vec2::zero()), 7);
+        assert_eq!(a.chebyshev(Vec2::zero()), 4);
+        // Unsigned points can't go negative along the way
+        assert_eq!(Point::<u64>::new(1, 5).manhattan(Point::new(4, 2)), 6);
+    }
+
+    #[test]
+    fn axis_conventions() {
+        assert_eq!(Direction::Up.screen(), Vec2::new(0, -1));
+        assert_eq!(Direction::Up.math(), Vec2::new(0, 1));
+        assert_eq!(Direction::Left.screen(), Direction::Left.math());
+        for dir in Direction::ALL {
+            assert_eq!(dir.screen(), -dir.opposite().screen());
+            assert_eq!(dir.rotate_cw().rotate_ccw(), dir);
+        }
+        assert_eq!(Direction::Up.rotate_cw(), Direction::Right);
+    }
+}
@@ -0,0 +1,409 @@
+//! A rectangular grid of cells, stored row by row in one vector.
+//!
+//! Cells are addressed by [`Vec2`] coordinates on screen axes, with y
+//! increasing down the grid the way puzzle inputs are laid out (see
+//! [`geometry`](crate::geometry)). The top left cell doesn't have
+//! to be at (0,0): a grid can be created with an offset so that it covers
+//! just the interesting part of a larger coordinate space, and every method
+//! takes and returns coordinates in that space.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Direction, ParseError, Vec2};
+
+// The directions to the 4 cells sharing an edge with a cell, and then the 4
+// more sharing only a corner
+const NEIGHBOURS: [Vec2; 8] = [
+    Direction::Up.screen(),
+    Direction::Right.screen(),
+    Direction::Down.screen(),
+    Direction::Left.screen(),
+    Vec2::new(1, -1),
+    Vec2::new(1, 1),
+    Vec2::new(-1, 1),
+    Vec2::new(-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    dims: Vec2,
+    // Coordinates of the top left cell
+    offset: Vec2,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid of the given width and height with every cell set to `fill`,
+    /// and its top left cell at (0,0).
+    pub fn new(dims: Vec2, fill: T) -> Self {
+        Self::with_offset(dims, Vec2::zero(), fill)
+    }
+
+    /// A grid of the given width and height with every cell set to `fill`,
+    /// and its top left cell at `offset`.
+    pub fn with_offset(dims: Vec2, offset: Vec2, fill: T) -> Self {
+        assert!(dims.x >= 0 && dims.y >= 0, "Grid can't have size {}", dims);
+        Self {
+            cells: vec![fill; dims.x as usize * dims.y as usize],
+            dims,
+            offset,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse a grid with one cell per character, where every line of `input`
+    /// is a row and they must all be the same length. `f` turns a character
+    /// into a cell, and its errors are reported against that character.
+    pub fn parse<F>(input: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(Vec2, char) -> Result<T, String>,
+    {
+        let too_big = || ParseError::new(input, input, "Grid is too big");
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let y = i32::try_from(y).map_err(|_| too_big())?;
+            let mut x = 0;
+            for (idx, c) in line.char_indices() {
+                let cell = f(Vec2 { x, y }, c)
+                    .map_err(|msg| ParseError::new(input, &line[idx..idx + c.len_utf8()], msg))?;
+                cells.push(cell);
+                x = x.checked_add(1).ok_or_else(too_big)?;
+            }
+            match width {
+                None => width = Some(x),
+                Some(width) if width != x => {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        format!("Expected every row to be {} long, got {}", width, x),
+                    ))
+                }
+                _ => {}
+            }
+            height = y + 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                cells,
+                dims: Vec2 {
+                    x: width,
+                    y: height,
+                },
+                offset: Vec2::zero(),
+            }),
+            _ => Err(ParseError::new(input, input, "Expected a grid")),
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.dims.x
+    }
+
+    pub fn height(&self) -> i32 {
+        self.dims.y
+    }
+
+    pub fn dims(&self) -> Vec2 {
+        self.dims
+    }
+
+    /// Coordinates of the top left cell
+    pub fn offset(&self) -> Vec2 {
+        self.offset
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: Vec2) -> bool {
+        let coord = coord - self.offset;
+        coord.x >= 0 && coord.x < self.dims.x && coord.y >= 0 && coord.y < self.dims.y
+    }
+
+    // Where the cell at `coord` is stored, if it's in the grid
+    fn index_of(&self, coord: Vec2) -> Option<usize> {
+        if !self.contains(coord) {
+            return None;
+        }
+        let coord = coord - self.offset;
+        Some(coord.y as usize * self.dims.x as usize + coord.x as usize)
+    }
+
+    // The inverse of `index_of`
+    fn coord_of(&self, idx: usize) -> Vec2 {
+        let width = self.dims.x as usize;
+        self.offset
+            + Vec2 {
+                x: (idx % width) as i32,
+                y: (idx / width) as i32,
+            }
+    }
+
+    pub fn get(&self, coord: Vec2) -> Option<&T> {
+        self.index_of(coord).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, coord: Vec2) -> Option<&mut T> {
+        self.index_of(coord).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Every cell along with its coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.coord_of(idx), cell))
+    }
+
+    /// The coordinates of every cell, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (0..self.cells.len()).map(|idx| self.coord_of(idx))
+    }
+
+    /// The cells sharing an edge with `coord` that are in the grid.
+    pub fn neighbours4(&self, coord: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.neighbours(coord, &NEIGHBOURS[..4])
+    }
+
+    /// The cells sharing an edge or a corner with `coord` that are in the
+    /// grid.
+    pub fn neighbours8(&self, coord: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.neighbours(coord, &NEIGHBOURS)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        coord: Vec2,
+        deltas: &'static [Vec2],
+    ) -> impl Iterator<Item = Vec2> + 'a {
+        deltas
+            .iter()
+            .map(move |&delta| coord + delta)
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The cells in row `y` from left to right, which can be reversed to go
+    /// from right to left. Panics if the row isn't in the grid.
+    pub fn row(
+        &self,
+        y: i32,
+    ) -> impl DoubleEndedIterator<Item = (Vec2, &T)> + ExactSizeIterator + '_ {
+        let start = self
+            .index_of(Vec2 {
+                x: self.offset.x,
+                y,
+            })
+            .unwrap_or_else(|| panic!("Row {} is not in the grid", y));
+        let xs = self.offset.x..self.offset.x + self.dims.x;
+        xs.zip(&self.cells[start..start + self.dims.x as usize])
+            .map(move |(x, cell)| (Vec2 { x, y }, cell))
+    }
+
+    /// The cells in column `x` from top to bottom, which can be reversed to
+    /// go from bottom to top. Panics if the column isn't in the grid.
+    pub fn column(
+        &self,
+        x: i32,
+    ) -> impl DoubleEndedIterator<Item = (Vec2, &T)> + ExactSizeIterator + '_ {
+        let start = self
+            .index_of(Vec2 {
+                x,
+                y: self.offset.y,
+            })
+            .unwrap_or_else(|| panic!("Column {} is not in the grid", x));
+        let ys = self.offset.y..self.offset.y + self.dims.y;
+        ys.zip(self.cells[start..].iter().step_by(self.dims.x as usize))
+            .map(move |(y, cell)| (Vec2 { x, y }, cell))
+    }
+
+    /// Each row of the grid, from top to bottom.
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = (Vec2, &T)> + ExactSizeIterator> {
+        (self.offset.y..self.offset.y + self.dims.y).map(|y| self.row(y))
+    }
+
+    /// Each column of the grid, from left to right.
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = (Vec2, &T)> + ExactSizeIterator> {
+        (self.offset.x..self.offset.x + self.dims.x).map(|x| self.column(x))
+    }
+
+    /// A view of the part of the grid with its top left cell at `min` and the
+    /// given width and height. The view is cut down to fit in the grid.
+    pub fn view(&self, min: Vec2, dims: Vec2) -> View<'_, T> {
+        let grid_max = self.offset + self.dims;
+        let max = min + dims;
+        let min = Vec2 {
+            x: min.x.clamp(self.offset.x, grid_max.x),
+            y: min.y.clamp(self.offset.y, grid_max.y),
+        };
+        let max = Vec2 {
+            x: max.x.clamp(min.x, grid_max.x),
+            y: max.y.clamp(min.y, grid_max.y),
+        };
+        View {
+            grid: self,
+            min,
+            dims: max - min,
+        }
+    }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Vec2) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{} is not in the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, coord: Vec2) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{} is not in the grid", coord))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.view(self.offset, self.dims).fmt(f)
+    }
+}
+
+/// A rectangular part of a [`Grid`], see [`Grid::view`]. Coordinates are the
+/// same as in the grid.
+#[derive(Clone, Copy, Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    min: Vec2,
+    dims: Vec2,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn dims(&self) -> Vec2 {
+        self.dims
+    }
+
+    /// Coordinates of the top left cell
+    pub fn offset(&self) -> Vec2 {
+        self.min
+    }
+
+    pub fn contains(&self, coord: Vec2) -> bool {
+        let coord = coord - self.min;
+        coord.x >= 0 && coord.x < self.dims.x && coord.y >= 0 && coord.y < self.dims.y
+    }
+
+    pub fn get(&self, coord: Vec2) -> Option<&'a T> {
+        if !self.contains(coord) {
+            return None;
+        }
+        self.grid.get(coord)
+    }
+
+    /// Each row of the view, from top to bottom.
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = (Vec2, &'a T)> + ExactSizeIterator>
+    {
+        let (min, dims, grid) = (self.min, self.dims, self.grid);
+        (min.y..min.y + dims.y).map(move |y| {
+            grid.row(y)
+                .skip((min.x - grid.offset.x) as usize)
+                .take(dims.x as usize)
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (_, cell) in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |_, c| {
+            c.to_digit(10)
+                .ok_or_else(|| format!("'{}' is not a digit", c))
+        })
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!(grid.dims(), Vec2 { x: 3, y: 2 });
+        assert_eq!(grid[Vec2 { x: 2, y: 1 }], 6);
+        assert_eq!(grid.get(Vec2 { x: 3, y: 0 }), None);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let err = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert!(digits("123\n45\n").is_err());
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn offset_coordinates() {
+        let mut grid = Grid::with_offset(Vec2 { x: 3, y: 2 }, Vec2 { x: -1, y: 10 }, '.');
+        assert!(grid.get(Vec2 { x: 0, y: 0 }).is_none());
+        *grid.get_mut(Vec2 { x: -1, y: 10 }).unwrap() = '#';
+        grid[Vec2 { x: 1, y: 11 }] = 'o';
+        assert_eq!(grid.to_string(), "#..\n..o");
+        assert_eq!(grid.iter().last(), Some((Vec2 { x: 1, y: 11 }, &'o')));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::new(Vec2 { x: 3, y: 3 }, 0);
+        assert_eq!(grid.neighbours4(Vec2 { x: 0, y: 0 }).count(), 2);
+        assert_eq!(grid.neighbours8(Vec2 { x: 0, y: 0 }).count(), 3);
+        assert_eq!(grid.neighbours4(Vec2 { x: 1, y: 1 }).count(), 4);
+        assert_eq!(grid.neighbours8(Vec2 { x: 1, y: 1 }).count(), 8);
+    }
+
+    #[test]
+    fn rows_columns_and_views() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        let column: Vec<u32> = grid.column(1).rev().map(|(_, &c)| c).collect();
+        assert_eq!(column, [8, 5, 2]);
+        let row: Vec<Vec2> = grid
+            .rows()
+            .nth(2)
+            .unwrap()
+            .map(|(coord, _)| coord)
+            .collect();
+        assert_eq!(row[0], Vec2 { x: 0, y: 2 });
+        assert_eq!(grid.columns().count(), 3);
+
+        let view = grid.view(Vec2 { x: 1, y: 1 }, Vec2 { x: 5, y: 5 });
+        assert_eq!(view.dims(), Vec2 { x: 2, y: 2 });
+        assert_eq!(view.get(Vec2 { x: 0, y: 0 }), None);
+        assert_eq!(view.to_string(), "56\n89");
+    }
+}
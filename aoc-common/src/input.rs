@@ -0,0 +1,50 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Whether `path` means stdin rather than a file. Like most command line
+/// tools, a missing path or `-` reads from stdin.
+pub fn is_stdin<P: AsRef<Path>>(path: Option<P>) -> bool {
+    match path {
+        Some(path) => path.as_ref() == Path::new("-"),
+        None => true,
+    }
+}
+
+/// Read the whole puzzle input from `path`, or from stdin if there's no path
+/// or it's `-`.
+pub fn read_input<P: AsRef<Path>>(path: Option<P>) -> io::Result<String> {
+    match path {
+        Some(path) if !is_stdin(Some(&path)) => fs::read_to_string(path),
+        _ => read_all(io::stdin().lock()),
+    }
+}
+
+/// Read everything from `reader` into a string, for parsing.
+pub fn read_all<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn dash_or_nothing_is_stdin() {
+        assert!(is_stdin(None::<&Path>));
+        assert!(is_stdin(Some("-")));
+        assert!(!is_stdin(Some(PathBuf::from("day1/src/input.txt"))));
+    }
+
+    #[test]
+    fn reads_everything() {
+        let input = read_all(io::Cursor::new("1000\n2000\n\n3000\n")).unwrap();
+        assert_eq!(input, "1000\n2000\n\n3000\n");
+    }
+}
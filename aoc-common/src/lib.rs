@@ -0,0 +1,20 @@
+//! Code shared between the solutions for each day. Anything that ends up
+//! being copied from one day into another should live here instead.
+
+pub mod diagnostics;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parser;
+pub mod part;
+pub mod solution;
+pub mod top_k;
+pub mod visual;
+
+pub use error::{NoSolutionError, ParseError};
+pub use geometry::{Direction, Point, Vec2};
+pub use grid::Grid;
+pub use part::Part;
+pub use solution::{Solution, Solver};
+pub use top_k::TopK;
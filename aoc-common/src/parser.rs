@@ -0,0 +1,413 @@
+//! Small parser combinators for the puzzle input formats.
+//!
+//! A parser takes the text still to be parsed, and returns what it parsed
+//! along with the rest of the text. Parsers are just functions, so they can
+//! be built up from the ones here or written by hand, e.g. a line like
+//! `2-4,6-8` can be parsed with
+//!
+//! ```
+//! use aoc_common::parser::{self, pair, preceded, tag, uint};
+//!
+//! let range = || pair(uint::<u32>(), preceded(tag("-"), uint()));
+//! let line = pair(range(), preceded(tag(","), range()));
+//! let input = "2-4,6-8\n";
+//! assert_eq!(parser::lines(input, &line).unwrap(), [((2, 4), (6, 8))]);
+//! ```
+//!
+//! Parsers never copy the input, so a [`Failure`] holds the bit of the input
+//! that was wrong. [`parse_all`] and [`lines`] turn that into a [`ParseError`]
+//! saying where it is in the whole input.
+
+use std::{fmt, str::FromStr};
+
+use crate::ParseError;
+
+/// Why a parser failed, and the text it failed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub at: &'a str,
+    pub message: String,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new<M: Into<String>>(at: &'a str, message: M) -> Self {
+        Self {
+            at,
+            message: message.into(),
+        }
+    }
+
+    /// Say where the failure is in `input`, which it must be part of.
+    pub fn into_error(self, input: &str) -> ParseError {
+        ParseError::new(input, self.at, self.message)
+    }
+}
+
+/// What was parsed and the rest of the text, or why it couldn't be parsed.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, s: &'a str) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, s: &'a str) -> PResult<'a, T> {
+        self(s)
+    }
+}
+
+// The bit of `s` to point at when it isn't what we expected. That's the next
+// word, or just the next character if that's whitespace.
+fn token(s: &str) -> &str {
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    match s.chars().next() {
+        Some(c) if end == 0 => &s[..c.len_utf8()],
+        _ => &s[..end],
+    }
+}
+
+// Complain that `s` isn't what we expected
+fn unexpected(s: &str) -> Failure<'_> {
+    let token = token(s);
+    if token.trim().is_empty() {
+        Failure::new(token, "Unexpected end of line")
+    } else {
+        Failure::new(token, format!("Unexpected '{}'", token))
+    }
+}
+
+/// Exactly the text `lit`.
+pub fn tag<'a>(lit: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |s: &'a str| match s.strip_prefix(lit) {
+        Some(rest) => Ok((&s[..lit.len()], rest)),
+        None => Err(Failure::new(token(s), format!("Expected '{}'", lit))),
+    }
+}
+
+/// Any number of spaces or tabs, but not newlines.
+pub fn spaces<'a>() -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    |s: &'a str| {
+        let rest = s.trim_start_matches([' ', '\t']);
+        Ok((&s[..s.len() - rest.len()], rest))
+    }
+}
+
+/// The end of a line, or of the input.
+pub fn line_end<'a>() -> impl Fn(&'a str) -> PResult<'a, ()> {
+    |s: &'a str| {
+        if s.is_empty() {
+            return Ok(((), s));
+        }
+        match s.strip_prefix('\n').or_else(|| s.strip_prefix("\r\n")) {
+            Some(rest) => Ok(((), rest)),
+            None => Err(unexpected(s)),
+        }
+    }
+}
+
+// Parse the number made of the first `len` bytes of `s`
+fn number<T>(s: &str, len: usize) -> PResult<'_, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let digits = &s[..len];
+    if !digits.ends_with(|c: char| c.is_ascii_digit()) {
+        return Err(Failure::new(token(s), "Expected a number"));
+    }
+    match digits.parse() {
+        Ok(value) => Ok((value, &s[len..])),
+        Err(err) => Err(Failure::new(digits, format!("'{}': {}", digits, err))),
+    }
+}
+
+/// A number made of one or more digits, with no sign.
+pub fn uint<'a, T>() -> impl Fn(&'a str) -> PResult<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    |s: &'a str| {
+        let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        number(s, len)
+    }
+}
+
+/// A number made of one or more digits, which might start with a '-'.
+pub fn int<'a, T>() -> impl Fn(&'a str) -> PResult<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    |s: &'a str| {
+        let sign = usize::from(s.starts_with('-'));
+        let len = s[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(s.len(), |len| sign + len);
+        number(s, len)
+    }
+}
+
+/// One of the given tokens, giving the value that goes with it. Tokens are
+/// tried in order, so if one is the start of another it should come later.
+pub fn one_of<'a, T: Copy>(
+    options: &'static [(&'static str, T)],
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |s: &'a str| {
+        for (lit, value) in options {
+            if let Some(rest) = s.strip_prefix(lit) {
+                return Ok((*value, rest));
+            }
+        }
+        let expected: Vec<String> = options
+            .iter()
+            .map(|(lit, _)| format!("'{}'", lit))
+            .collect();
+        Err(Failure::new(
+            token(s),
+            format!("Expected one of {}", expected.join(", ")),
+        ))
+    }
+}
+
+/// Both parsers, one after the other.
+pub fn pair<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Fn(&'a str) -> PResult<'a, (T, U)> {
+    move |s: &'a str| {
+        let (a, s) = first.parse(s)?;
+        let (b, s) = second.parse(s)?;
+        Ok(((a, b), s))
+    }
+}
+
+/// Both parsers, keeping what the second one parsed.
+pub fn preceded<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Fn(&'a str) -> PResult<'a, U> {
+    move |s: &'a str| {
+        let (_, s) = first.parse(s)?;
+        second.parse(s)
+    }
+}
+
+/// Both parsers, keeping what the first one parsed.
+pub fn terminated<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |s: &'a str| {
+        let (a, s) = first.parse(s)?;
+        let (_, s) = second.parse(s)?;
+        Ok((a, s))
+    }
+}
+
+/// Whichever parser works, trying `first` first. If neither does, the
+/// failure that got furthest into the text is kept.
+pub fn or<'a, T>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |s: &'a str| {
+        let err1 = match first.parse(s) {
+            Ok(res) => return Ok(res),
+            Err(err) => err,
+        };
+        second.parse(s).map_err(|err2| {
+            if err1.at.as_ptr() > err2.at.as_ptr() {
+                err1
+            } else {
+                err2
+            }
+        })
+    }
+}
+
+/// Turn what `p` parsed into something else.
+pub fn map<'a, T, U, F>(p: impl Parser<'a, T>, f: F) -> impl Fn(&'a str) -> PResult<'a, U>
+where
+    F: Fn(T) -> U,
+{
+    move |s: &'a str| {
+        let (value, rest) = p.parse(s)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Turn what `p` parsed into something else, or complain about all of the
+/// text it parsed if that's not possible.
+pub fn try_map<'a, T, U, F>(p: impl Parser<'a, T>, f: F) -> impl Fn(&'a str) -> PResult<'a, U>
+where
+    F: Fn(T) -> Result<U, String>,
+{
+    move |s: &'a str| {
+        let (value, rest) = p.parse(s)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(message) => Err(Failure::new(&s[..s.len() - rest.len()], message)),
+        }
+    }
+}
+
+/// What `p` parsed, along with the text it parsed, for checks that need to
+/// complain about it later.
+pub fn spanned<'a, T>(p: impl Parser<'a, T>) -> impl Fn(&'a str) -> PResult<'a, (T, &'a str)> {
+    move |s: &'a str| {
+        let (value, rest) = p.parse(s)?;
+        Ok(((value, &s[..s.len() - rest.len()]), rest))
+    }
+}
+
+// The rest of a list after its first item, which must be followed by another
+// item after every separator
+fn rest_of_list<'a, T, U, P, S>(item: &P, sep: &S, first: T, mut s: &'a str) -> PResult<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+    S: Parser<'a, U>,
+{
+    let mut items = vec![first];
+    while let Ok((_, rest)) = sep.parse(s) {
+        let (next, rest) = item.parse(rest)?;
+        items.push(next);
+        s = rest;
+    }
+    Ok((items, s))
+}
+
+/// Items separated by `sep`, like `1, 2, 3`. There might be no items at all,
+/// but there must be an item after every separator.
+pub fn separated<'a, T, U>(
+    item: impl Parser<'a, T>,
+    sep: impl Parser<'a, U>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |s: &'a str| match item.parse(s) {
+        Ok((first, rest)) => rest_of_list(&item, &sep, first, rest),
+        Err(_) => Ok((vec![], s)),
+    }
+}
+
+/// Like [`separated`], but there must be at least one item.
+pub fn separated1<'a, T, U>(
+    item: impl Parser<'a, T>,
+    sep: impl Parser<'a, U>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, rest) = item.parse(s)?;
+        rest_of_list(&item, &sep, first, rest)
+    }
+}
+
+/// A whole line like `  Test: divisible by 23`, keeping what `value` parsed
+/// after the colon. Spaces around the key and value are skipped.
+pub fn key_value<'a, T>(
+    key: &'static str,
+    value: impl Parser<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    let key = preceded(spaces(), terminated(tag(key), pair(tag(":"), spaces())));
+    move |s: &'a str| {
+        let (_, s) = key.parse(s)?;
+        let (value, s) = value.parse(s)?;
+        let (_, s) = spaces().parse(s)?;
+        let (_, s) = line_end().parse(s)?;
+        Ok((value, s))
+    }
+}
+
+/// Parse all of `s`, which is part of `input`, complaining if anything is
+/// left over.
+pub fn parse_all<'a, T, P>(input: &str, s: &'a str, p: &P) -> Result<T, ParseError>
+where
+    P: Parser<'a, T>,
+{
+    let (value, rest) = p.parse(s).map_err(|err| err.into_error(input))?;
+    if !rest.is_empty() {
+        return Err(unexpected(rest).into_error(input));
+    }
+    Ok(value)
+}
+
+/// Parse every line of `input` with `p`.
+pub fn lines<'a, T, P>(input: &'a str, p: &P) -> Result<Vec<T>, ParseError>
+where
+    P: Parser<'a, T>,
+{
+    input
+        .lines()
+        .map(|line| parse_all(input, line, p))
+        .collect()
+}
+
+/// Split `input` into blocks of lines separated by blank lines. Each block
+/// is a slice of `input`, without the newline at the end.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(uint::<u32>()("42 rest"), Ok((42, " rest")));
+        assert_eq!(int::<i32>()("-42"), Ok((-42, "")));
+        assert!(uint::<u32>()("-42").is_err());
+        assert!(int::<i32>()("-").is_err());
+        assert!(uint::<u8>()("256").is_err());
+    }
+
+    #[test]
+    fn lists_and_keys() {
+        let items = key_value("Starting items", separated(uint::<u32>(), tag(", ")));
+        assert_eq!(
+            items("  Starting items: 79, 98\nx"),
+            Ok((vec![79, 98], "x"))
+        );
+        assert_eq!(items("  Starting items:"), Ok((vec![], "")));
+        assert!(items("  Starting items: 79, x").is_err());
+        assert!(items("  Starting things: 79").is_err());
+        assert!(separated1(uint::<u32>(), tag(","))("x").is_err());
+    }
+
+    #[test]
+    fn errors_point_into_input() {
+        let input = "1-2\n3-x\n";
+        let range = pair(uint::<u32>(), preceded(tag("-"), uint::<u32>()));
+        let err = lines(input, &range).unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (2, 3, 1));
+
+        let err = lines("1-2 3\n", &range).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "a\nb\n\n\nc\r\n\nd";
+        assert_eq!(blocks(input), ["a\nb", "c", "d"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+}
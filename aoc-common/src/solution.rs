@@ -0,0 +1,182 @@
+use std::{any::Any, error::Error, fmt, fs, io::BufRead, path::Path};
+
+use crate::{input, Part};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Every day implements this. Parsing is split out from solving so that
+/// both parts can share one parsed input, and so that each phase can be
+/// run (and tested) on its own.
+pub trait Solution {
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    /// The parts this day solves. A day that only has part 1 so far can
+    /// leave part 2 out here, and its `part2` is never called.
+    const PARTS: &'static [Part] = &Part::ALL;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Parse input from any reader, e.g. stdin or a generated input in a
+    /// `Cursor`, rather than a string that's already in memory.
+    fn parse_reader<R: BufRead>(&self, reader: R) -> Result<Self::Input>
+    where
+        Self: Sized,
+    {
+        self.parse(&input::read_all(reader)?)
+    }
+
+    /// Parse the input in the file at `path`. Days with inputs that can be
+    /// huge can do better than reading it all in first.
+    fn parse_file(&self, path: &Path) -> Result<Self::Input>
+    where
+        Self: Sized,
+    {
+        self.parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// Object safe version of `Solution`, so that the runner can keep every
+/// day in one table. Answers are rendered to strings.
+pub trait Solver {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>>;
+    fn parse_file(&self, path: &Path) -> Result<Box<dyn Any>>;
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<String>;
+    fn parts(&self) -> &'static [Part];
+}
+
+/// Asked for a part the solver doesn't have.
+#[derive(Debug, Clone)]
+pub struct UnimplementedPartError(pub Part);
+
+impl Error for UnimplementedPartError {}
+
+impl fmt::Display for UnimplementedPartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Part {} is not implemented", self.0)
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Result<Box<dyn Any>> {
+        Solver::parse(self, &input::read_all(reader)?)
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse_file(self, path)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<String> {
+        if !S::PARTS.contains(&part) {
+            return Err(Box::new(UnimplementedPartError(part)));
+        }
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input should have come from this solver's parse");
+        Ok(match part {
+            Part::One => self.part1(input)?.to_string(),
+            Part::Two => self.part2(input)?.to_string(),
+        })
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+}
+
+/// Parse `input` and solve every implemented part of it, stopping at the first error.
+/// Mostly useful for tests, which want to know that a bad input gives an
+/// error rather than a panic wherever it's found.
+pub fn solve_all(solver: &dyn Solver, input: &str) -> Result<Vec<String>> {
+    let parsed = solver.parse(input)?;
+    solver
+        .parts()
+        .iter()
+        .map(|&part| solver.solve(parsed.as_ref(), part))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    // Adds up the numbers on each line
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = usize;
+
+        fn parse(&self, input: &str) -> Result<Self::Input> {
+            Ok(input
+                .lines()
+                .map(|line| line.parse())
+                .collect::<std::result::Result<_, _>>()?)
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<i32> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<usize> {
+            Ok(input.len())
+        }
+    }
+
+    // Only has part 1 so far
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        type Input = ();
+        type Answer1 = i32;
+        type Answer2 = i32;
+        const PARTS: &'static [Part] = &[Part::One];
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &()) -> Result<i32> {
+            Ok(1)
+        }
+
+        fn part2(&self, _input: &()) -> Result<i32> {
+            unreachable!("Part 2 isn't implemented")
+        }
+    }
+
+    #[test]
+    fn only_solves_implemented_parts() {
+        let solver: &dyn Solver = &Unfinished;
+        assert_eq!(solver.parts(), [Part::One]);
+        assert_eq!(solve_all(solver, "").unwrap(), ["1"]);
+        assert!(solver.solve(&(), Part::Two).is_err());
+        assert_eq!((&Sum as &dyn Solver).parts(), Part::ALL);
+    }
+
+    #[test]
+    fn parses_from_a_reader() {
+        let input = Solution::parse_reader(&Sum, Cursor::new("1\n2\n3\n")).unwrap();
+        assert_eq!(input, [1, 2, 3]);
+
+        let solver: &dyn Solver = &Sum;
+        let parsed = solver.parse_reader(&mut Cursor::new("4\n5\n")).unwrap();
+        assert_eq!(solver.solve(parsed.as_ref(), Part::One).unwrap(), "9");
+        assert!(solver.parse_reader(&mut Cursor::new("x\n")).is_err());
+    }
+}
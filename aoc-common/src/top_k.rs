@@ -0,0 +1,188 @@
+//! Keeping the K largest values from a stream without holding on to the rest.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// Up to this many values, finding the smallest by looking through them all
+// is quicker than keeping a heap in order
+const LINEAR_MAX: usize = 16;
+// Don't set aside room for more than this many values up front, as K can be
+// far more than will ever be pushed. Bigger heaps grow as values arrive.
+const HEAP_PREALLOCATE_MAX: usize = 1024;
+
+#[derive(Clone, Debug)]
+enum Store<T> {
+    // Unordered, with the smallest found by a scan when it needs replacing
+    Linear(Vec<T>),
+    // A min-heap, so the smallest is always on top
+    Heap(BinaryHeap<Reverse<T>>),
+}
+
+/// The K largest values pushed so far. Memory use is bounded by K however
+/// many values are pushed, so it's suited to streams.
+///
+/// ```
+/// use aoc_common::TopK;
+///
+/// let mut top = TopK::new(3);
+/// top.extend([5, 1, 9, 3, 7]);
+/// assert_eq!(top.into_sorted_vec(), [9, 7, 5]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TopK<T: Ord> {
+    k: usize,
+    store: Store<T>,
+}
+
+impl<T: Ord> TopK<T> {
+    /// Keep the `k` largest values. Small values of `k` are kept in a plain
+    /// list, and larger ones in a heap. Memory is only used for the values
+    /// actually kept, so a huge `k` is fine.
+    pub fn new(k: usize) -> Self {
+        let store = if k <= LINEAR_MAX {
+            Store::Linear(Vec::with_capacity(k))
+        } else {
+            Store::Heap(BinaryHeap::with_capacity(k.min(HEAP_PREALLOCATE_MAX)))
+        };
+        Self { k, store }
+    }
+
+    /// How many values are kept at most.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// How many values are kept at the moment, which is K unless fewer than
+    /// K have been pushed.
+    pub fn len(&self) -> usize {
+        match &self.store {
+            Store::Linear(values) => values.len(),
+            Store::Heap(heap) => heap.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Keep `value` if it's one of the K largest so far, dropping whichever
+    /// value it replaces.
+    pub fn push(&mut self, value: T) {
+        if self.k == 0 {
+            return;
+        }
+        match &mut self.store {
+            Store::Linear(values) if values.len() < self.k => values.push(value),
+            Store::Linear(values) => {
+                let smallest = values.iter_mut().min().unwrap();
+                if *smallest < value {
+                    *smallest = value;
+                }
+            }
+            Store::Heap(heap) if heap.len() < self.k => heap.push(Reverse(value)),
+            Store::Heap(heap) => {
+                let mut smallest = heap.peek_mut().unwrap();
+                if smallest.0 < value {
+                    *smallest = Reverse(value);
+                }
+            }
+        }
+    }
+
+    /// Add the values kept by `other`, e.g. from another part of the
+    /// stream. This keeps this one's K.
+    pub fn merge(&mut self, other: TopK<T>) {
+        self.extend(other.into_values());
+    }
+
+    fn into_values(self) -> Vec<T> {
+        match self.store {
+            Store::Linear(values) => values,
+            Store::Heap(heap) => heap.into_iter().map(|Reverse(value)| value).collect(),
+        }
+    }
+
+    /// The values kept, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut values = self.into_values();
+        values.sort_by(|a, b| b.cmp(a));
+        values
+    }
+}
+
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // The obvious way to get the top K, to check against
+    fn by_sorting(values: &[i32], k: usize) -> Vec<i32> {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| b.cmp(a));
+        sorted.truncate(k);
+        sorted
+    }
+
+    #[test]
+    fn keeps_fewer_than_k_if_thats_all_there_is() {
+        let mut top = TopK::new(3);
+        assert!(top.is_empty());
+        top.extend([2, 1]);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top.into_sorted_vec(), [2, 1]);
+
+        let mut none = TopK::new(0);
+        none.push(1);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn huge_k_keeps_everything() {
+        let mut top = TopK::new(usize::MAX);
+        top.extend([3, 1, 2]);
+        assert_eq!(top.k(), usize::MAX);
+        assert_eq!(top.into_sorted_vec(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn keeps_duplicates() {
+        let mut top = TopK::new(3);
+        top.extend([4, 4, 1, 4, 4]);
+        assert_eq!(top.into_sorted_vec(), [4, 4, 4]);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_sorting(
+            values in prop::collection::vec(-1000..1000i32, 0..100),
+            k in 0..40usize,
+        ) {
+            let mut top = TopK::new(k);
+            top.extend(values.iter().copied());
+            prop_assert_eq!(top.into_sorted_vec(), by_sorting(&values, k));
+        }
+
+        #[test]
+        fn merging_matches_one_stream(
+            values in prop::collection::vec(-1000..1000i32, 0..100),
+            split in 0..100usize,
+            k in 0..40usize,
+        ) {
+            let (left, right) = values.split_at(split.min(values.len()));
+            let mut top = TopK::new(k);
+            top.extend(left.iter().copied());
+            let mut other = TopK::new(k);
+            other.extend(right.iter().copied());
+            top.merge(other);
+            prop_assert_eq!(top.into_sorted_vec(), by_sorting(&values, k));
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Pictures of a solution's state as it goes, for watching a simulation run.
+//!
+//! Solutions call [`frame`] at each interesting step with a closure that
+//! draws the current state. Nothing gets drawn unless the runner is
+//! [`watch`]ing with a [`Sink`] on the same thread, so frames cost next to
+//! nothing the rest of the time.
+
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+};
+
+use tracing::warn;
+
+use crate::Grid;
+
+thread_local! {
+    static SINK: RefCell<Option<Box<dyn Sink>>> = const { RefCell::new(None) };
+}
+
+/// The colours a cell can be drawn in. Sinks without colour ignore them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Default,
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    /// The ANSI escape code to switch to this colour.
+    pub fn ansi(self) -> &'static str {
+        match self {
+            Colour::Default => "\x1b[0m",
+            Colour::Grey => "\x1b[90m",
+            Colour::Red => "\x1b[91m",
+            Colour::Green => "\x1b[92m",
+            Colour::Yellow => "\x1b[93m",
+            Colour::Blue => "\x1b[94m",
+            Colour::Magenta => "\x1b[95m",
+            Colour::Cyan => "\x1b[96m",
+        }
+    }
+}
+
+/// One picture of the state, as rows of coloured characters with a caption
+/// saying what's going on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub rows: Vec<Vec<(char, Colour)>>,
+}
+
+impl Frame {
+    /// A frame of some text, with each character coloured by `colour`.
+    pub fn text<F: Fn(char) -> Colour>(caption: &str, text: &str, colour: F) -> Frame {
+        Frame {
+            caption: String::from(caption),
+            rows: text
+                .lines()
+                .map(|line| line.chars().map(|c| (c, colour(c))).collect())
+                .collect(),
+        }
+    }
+
+    /// A frame of a grid, with `draw` picking the character and colour for
+    /// each cell.
+    pub fn grid<T, F: Fn(&T) -> (char, Colour)>(caption: &str, grid: &Grid<T>, draw: F) -> Frame {
+        Frame {
+            caption: String::from(caption),
+            rows: grid
+                .rows()
+                .map(|row| row.map(|(_, cell)| draw(cell)).collect())
+                .collect(),
+        }
+    }
+
+    /// The widest row.
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Each row as a line of text, starting `skip` characters in and at most
+    /// `width` long, with ANSI colour codes if `ansi` is set.
+    pub fn lines(&self, skip: usize, width: usize, ansi: bool) -> Vec<String> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut line = String::new();
+                let mut current = Colour::Default;
+                for &(c, colour) in row.iter().skip(skip).take(width) {
+                    if ansi && colour != current {
+                        line += colour.ansi();
+                        current = colour;
+                    }
+                    line.push(c);
+                }
+                if ansi && current != Colour::Default {
+                    line += Colour::Default.ansi();
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+/// Somewhere frames can be shown.
+pub trait Sink {
+    /// Show a frame, returning whether to carry on watching.
+    fn show(&mut self, frame: &Frame) -> io::Result<bool>;
+}
+
+/// A sink that writes each frame as plain text, with its caption above it
+/// and a blank line after, e.g. to a file for watching later.
+pub struct FrameWriter<W: Write>(pub W);
+
+impl<W: Write> Sink for FrameWriter<W> {
+    fn show(&mut self, frame: &Frame) -> io::Result<bool> {
+        writeln!(self.0, "{}", frame.caption)?;
+        for line in frame.lines(0, usize::MAX, false) {
+            writeln!(self.0, "{}", line)?;
+        }
+        writeln!(self.0)?;
+        Ok(true)
+    }
+}
+
+/// Whether anything is watching on this thread, for solutions that want to
+/// avoid extra work when it isn't.
+pub fn watching() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+/// Show the frame `draw` makes, if anything is watching.
+pub fn frame<F: FnOnce() -> Frame>(draw: F) {
+    if !watching() {
+        return;
+    }
+    let frame = draw();
+    SINK.with(|sink| {
+        let mut sink = sink.borrow_mut();
+        let Some(s) = sink.as_mut() else {
+            return;
+        };
+        match s.show(&frame) {
+            Ok(true) => {}
+            Ok(false) => *sink = None,
+            Err(err) => {
+                warn!("Stopped watching: {}", err);
+                *sink = None;
+            }
+        }
+    })
+}
+
+/// Run `f`, showing any frames it makes on this thread in `sink`, which is
+/// dropped at the end.
+pub fn watch<T, F: FnOnce() -> T>(sink: Box<dyn Sink>, f: F) -> T {
+    let outer = SINK.with(|s| s.replace(Some(sink)));
+    let res = f();
+    SINK.with(|s| s.replace(outer));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn colours_runs_of_cells() {
+        let frame = Frame::text("caption", "#o.\n..o", |c| match c {
+            '#' => Colour::Grey,
+            'o' => Colour::Yellow,
+            _ => Colour::Default,
+        });
+        assert_eq!(frame.width(), 3);
+        assert_eq!(
+            frame.lines(0, 3, true),
+            ["\x1b[90m#\x1b[93mo\x1b[0m.", "..\x1b[93mo\x1b[0m"]
+        );
+        assert_eq!(frame.lines(1, 1, false), ["o", "."]);
+    }
+
+    // Counts the frames it's shown, and stops after a few
+    struct Counter(Rc<Cell<usize>>);
+
+    impl Sink for Counter {
+        fn show(&mut self, _frame: &Frame) -> io::Result<bool> {
+            self.0.set(self.0.get() + 1);
+            Ok(self.0.get() < 3)
+        }
+    }
+
+    #[test]
+    fn only_draws_while_watched() {
+        let drawn = Cell::new(0);
+        let draw = || {
+            drawn.set(drawn.get() + 1);
+            Frame::default()
+        };
+        frame(draw);
+        assert_eq!(drawn.get(), 0);
+
+        let shown = Rc::new(Cell::new(0));
+        watch(Box::new(Counter(shown.clone())), || {
+            assert!(watching());
+            for _ in 0..5 {
+                frame(draw);
+            }
+            // The sink asked to stop after the third
+            assert!(!watching());
+        });
+        assert_eq!(shown.get(), 3);
+        assert_eq!(drawn.get(), 3);
+    }
+
+    // Somewhere to write that can be read back after the sink is gone
+    #[derive(Clone, Default)]
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_frames_as_text() {
+        let out = Shared::default();
+        watch(Box::new(FrameWriter(out.clone())), || {
+            frame(|| Frame::text("Step 1", "ab\ncd", |_| Colour::Red));
+            frame(|| Frame::text("Step 2", "ef", |_| Colour::Red));
+        });
+        assert_eq!(
+            String::from_utf8(out.0.take()).unwrap(),
+            "Step 1\nab\ncd\n\nStep 2\nef\n\n"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::{error::Error, fs, io, path::Path};
+
+use aoc_common::Part;
+
+// The answers we know to be right for a day, as recorded in its
+// answers.toml. They're keyed by the name of the input file without its
+// extension and then by part, e.g.
+//
+//   [example]
+//   part1 = 13
+//
+//   [input]
+//   part1 = 5208
+//   part2 = 25792
+//
+// Answers can be written as integers or strings, and pictures can use
+// multi-line strings.
+pub struct Answers(toml::Table);
+
+impl Answers {
+    // A day without an answers.toml just has no recorded answers
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Answers(contents.parse()?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers(toml::Table::new())),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+
+    pub fn get(&self, input: &str, part: Part) -> Option<String> {
+        let key = format!("part{}", part);
+        match self.0.get(input)?.get(key)? {
+            toml::Value::String(s) => Some(s.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+}
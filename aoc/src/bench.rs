@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt, fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{error, Part};
+
+use crate::{fetch::Inputs, registry};
+
+// The phases of a solution that are timed separately
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+struct Timing {
+    day: u32,
+    phase: Phase,
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+// Run `f` the given number of times and summarise how long it took
+fn time<F>(iterations: usize, mut f: F) -> Result<(Duration, Duration, Duration), Box<dyn Error>>
+where
+    F: FnMut() -> Result<(), Box<dyn Error>>,
+{
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    samples.sort();
+    Ok((
+        samples[0],
+        samples[samples.len() / 2],
+        samples[samples.len() - 1],
+    ))
+}
+
+fn bench_day(
+    entry: &registry::Day,
+    input: &str,
+    iterations: usize,
+) -> Result<Vec<Timing>, Box<dyn Error>> {
+    let mut timings = vec![];
+    let mut record = |phase, (min, median, max)| {
+        timings.push(Timing {
+            day: entry.day,
+            phase,
+            min,
+            median,
+            max,
+        })
+    };
+
+    record(
+        Phase::Parse,
+        time(iterations, || entry.solver.parse(input).map(|_| ()))?,
+    );
+
+    // Only parse once for the parts, so they're timed on their own
+    let parsed = entry.solver.parse(input)?;
+    for &part in entry.solver.parts() {
+        record(
+            Phase::Part(part),
+            time(iterations, || {
+                entry.solver.solve(parsed.as_ref(), part).map(|_| ())
+            })?,
+        );
+    }
+    Ok(timings)
+}
+
+#[derive(Debug, Clone)]
+pub struct BaselineParseError(String);
+
+impl Error for BaselineParseError {}
+
+impl fmt::Display for BaselineParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bad line in benchmark baseline: '{}'", self.0)
+    }
+}
+
+const CSV_HEADER: &str = "day,phase,min_ns,median_ns,max_ns";
+
+fn write_csv(path: &Path, timings: &[Timing]) -> Result<(), Box<dyn Error>> {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for t in timings {
+        csv += &format!(
+            "{},{},{},{},{}\n",
+            t.day,
+            t.phase,
+            t.min.as_nanos(),
+            t.median.as_nanos(),
+            t.max.as_nanos()
+        );
+    }
+    fs::write(path, csv)?;
+    Ok(())
+}
+
+// Read back the median times from a CSV written by a previous run, keyed by
+// day and phase name
+fn read_baseline(path: &Path) -> Result<HashMap<(u32, String), Duration>, Box<dyn Error>> {
+    let mut medians = HashMap::new();
+    for line in fs::read_to_string(path)?.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() != 5 {
+            return Err(Box::new(BaselineParseError(String::from(line))));
+        }
+        let day: u32 = fields[0].parse()?;
+        let median = Duration::from_nanos(fields[3].parse()?);
+        medians.insert((day, String::from(fields[1])), median);
+    }
+    Ok(medians)
+}
+
+// How much slower `median` is than `old`, as a percentage, or None if there's
+// nothing to compare against because `old` took no time at all
+fn change(median: Duration, old: Duration) -> Option<f64> {
+    if old.is_zero() {
+        return None;
+    }
+    Some((median.as_secs_f64() / old.as_secs_f64() - 1.0) * 100.0)
+}
+
+// Time the parse and both parts of each day (or just `day`) over a number of
+// iterations, on the day's input from `inputs`. The results can be saved
+// as CSV to `output`, and compared against the CSV from an earlier run.
+pub fn bench(
+    inputs: &Inputs,
+    day: Option<u32>,
+    iterations: usize,
+    output: Option<&Path>,
+    baseline: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let iterations = iterations.max(1);
+    let baseline = baseline.map(read_baseline).transpose()?;
+
+    let mut timings = vec![];
+    for entry in registry::DAYS {
+        if day.is_some_and(|day| day != entry.day) {
+            continue;
+        }
+        let path = inputs.path(entry.day)?;
+        let input = fs::read_to_string(&path)?;
+        timings.extend(
+            bench_day(entry, &input, iterations).map_err(|err| error::in_file(err, &path))?,
+        );
+    }
+
+    println!(
+        "{:>3}  {:<6} {:>12} {:>12} {:>12}{}",
+        "day",
+        "phase",
+        "min",
+        "median",
+        "max",
+        if baseline.is_some() {
+            "     baseline   change"
+        } else {
+            ""
+        }
+    );
+    for t in &timings {
+        print!(
+            "{:>3}  {:<6} {:>12} {:>12} {:>12}",
+            t.day,
+            t.phase,
+            format!("{:.2?}", t.min),
+            format!("{:.2?}", t.median),
+            format!("{:.2?}", t.max)
+        );
+        if let Some(baseline) = &baseline {
+            let old = baseline.get(&(t.day, t.phase.to_string()));
+            print!(
+                " {:>12} {:>8}",
+                old.map_or(String::from("-"), |old| format!("{:.2?}", old)),
+                match old.and_then(|&old| change(t.median, old)) {
+                    Some(change) => format!("{:+.1}%", change),
+                    None => String::from("n/a"),
+                }
+            );
+        }
+        println!();
+    }
+
+    if let Some(output) = output {
+        write_csv(output, &timings)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::temp_dir;
+
+    fn timing(day: u32, phase: Phase, median: u64) -> Timing {
+        Timing {
+            day,
+            phase,
+            min: Duration::from_nanos(median / 2),
+            median: Duration::from_nanos(median),
+            max: Duration::from_nanos(median * 2),
+        }
+    }
+
+    #[test]
+    fn reads_back_the_medians_it_wrote() {
+        let path = temp_dir("bench-csv").join("baseline.csv");
+        let timings = [
+            timing(1, Phase::Parse, 1500),
+            timing(1, Phase::Part(Part::One), 0),
+            timing(11, Phase::Part(Part::Two), 2_000_000_000),
+        ];
+        write_csv(&path, &timings).unwrap();
+        let medians = read_baseline(&path).unwrap();
+        assert_eq!(medians.len(), 3);
+        for t in &timings {
+            assert_eq!(medians[&(t.day, t.phase.to_string())], t.median);
+        }
+    }
+
+    #[test]
+    fn broken_baselines_are_an_error() {
+        let dir = temp_dir("bench-broken");
+        for csv in ["x\n1,parse,1,2", "x\n1,parse,1,two,3", "x\nday,parse,1,2,3"] {
+            let path = dir.join("baseline.csv");
+            fs::write(&path, csv).unwrap();
+            assert!(read_baseline(&path).is_err(), "{:?}", csv);
+        }
+    }
+
+    #[test]
+    fn compares_against_the_baseline() {
+        let ms = Duration::from_millis;
+        assert_eq!(change(ms(15), ms(10)), Some(50.0));
+        assert_eq!(change(ms(5), ms(10)), Some(-50.0));
+        assert_eq!(change(ms(5), Duration::ZERO), None);
+    }
+}
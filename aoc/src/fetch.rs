@@ -0,0 +1,330 @@
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+// What the server sent back, whatever the status
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+// Makes the requests to the site, so tests can swap in something that
+// doesn't need the real one.
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<Response, Box<dyn Error>>;
+    fn post(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn Error>>;
+}
+
+// The real backend
+pub struct Ureq(ureq::Agent);
+
+impl Ureq {
+    pub fn new() -> Ureq {
+        Ureq(
+            ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("aoc-2022-runner/", env!("CARGO_PKG_VERSION")))
+                .build(),
+        )
+    }
+}
+
+impl Ureq {
+    fn response(res: Result<ureq::Response, ureq::Error>) -> Result<Response, Box<dyn Error>> {
+        let res = match res {
+            Ok(res) => res,
+            // Error statuses still have a body worth reporting
+            Err(ureq::Error::Status(_, res)) => res,
+            Err(err) => return Err(Box::new(err)),
+        };
+        Ok(Response {
+            status: res.status(),
+            body: res.into_string()?,
+        })
+    }
+}
+
+impl HttpClient for Ureq {
+    fn get(&self, url: &str, session: &str) -> Result<Response, Box<dyn Error>> {
+        Ureq::response(
+            self.0
+                .get(url)
+                .set("Cookie", &format!("session={}", session))
+                .call(),
+        )
+    }
+
+    fn post(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> Result<Response, Box<dyn Error>> {
+        Ureq::response(
+            self.0
+                .post(url)
+                .set("Cookie", &format!("session={}", session))
+                .send_form(form),
+        )
+    }
+}
+
+// Says what we couldn't do without a session
+#[derive(Debug, Clone)]
+pub struct MissingSessionError(String);
+
+impl Error for MissingSessionError {}
+
+impl fmt::Display for MissingSessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "A session token is needed to {}. Set AOC_SESSION or pass --session",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FetchError {
+    url: String,
+    status: u16,
+    body: String,
+}
+
+impl Error for FetchError {}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Fetching {} failed with status {}",
+            self.url, self.status
+        )?;
+        // The site explains itself on the first line, e.g. that a puzzle isn't
+        // unlocked yet or that the session has expired
+        match self.body.lines().next() {
+            Some(line) if !line.trim().is_empty() => write!(f, ": {}", line.trim()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Where the site is and how politely to talk to it
+pub struct SiteConfig {
+    // The year's page, e.g. https://adventofcode.com/2022
+    pub base_url: String,
+    pub session: Option<String>,
+    // The least time to leave between requests
+    pub min_interval: Duration,
+}
+
+// The site for the year, which everything goes through so that the requests
+// are spaced out
+pub struct Site {
+    config: SiteConfig,
+    client: Box<dyn HttpClient>,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Site {
+    pub fn new(config: SiteConfig, client: Box<dyn HttpClient>) -> Site {
+        Site {
+            config,
+            client,
+            last_request: Cell::new(None),
+        }
+    }
+
+    // Get a page under the base URL, or post a form to it, failing unless the
+    // site says OK. `what` says what it's for if there's no session.
+    pub fn request(
+        &self,
+        page: &str,
+        form: Option<&[(&str, &str)]>,
+        what: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let session = self
+            .config
+            .session
+            .as_deref()
+            .ok_or_else(|| MissingSessionError(String::from(what)))?;
+
+        let url = format!("{}/{}", self.config.base_url.trim_end_matches('/'), page);
+        self.wait_turn();
+        let res = match form {
+            Some(form) => self.client.post(&url, session, form)?,
+            None => self.client.get(&url, session)?,
+        };
+        if res.status != 200 {
+            return Err(Box::new(FetchError {
+                url,
+                status: res.status,
+                body: res.body,
+            }));
+        }
+        Ok(res.body)
+    }
+
+    // Sleep until it's been long enough since the last request
+    fn wait_turn(&self) {
+        if let Some(last) = self.last_request.get() {
+            let next = last + self.config.min_interval;
+            let now = Instant::now();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+}
+
+// Finds each day's puzzle input, fetching and caching it the first time it's
+// asked for. The cache has the same layout as the workspace, with each input
+// in dayN/src/input.txt, so by default the committed inputs are the cache.
+pub struct Inputs<'a> {
+    cache: PathBuf,
+    site: &'a Site,
+}
+
+impl<'a> Inputs<'a> {
+    pub fn new(cache: &Path, site: &'a Site) -> Inputs<'a> {
+        Inputs {
+            cache: cache.to_path_buf(),
+            site,
+        }
+    }
+
+    // Where the day's own files go in the cache
+    pub fn day_dir(&self, day: u32) -> PathBuf {
+        self.cache.join(format!("day{}", day))
+    }
+
+    fn cache_path(&self, day: u32) -> PathBuf {
+        self.day_dir(day).join("src").join("input.txt")
+    }
+
+    // The day's input if it's already in the cache. New days start with an
+    // empty input.txt, which doesn't count.
+    pub fn cached(&self, day: u32) -> Option<PathBuf> {
+        let path = self.cache_path(day);
+        match fs::metadata(&path) {
+            Ok(meta) if meta.len() > 0 => Some(path),
+            _ => None,
+        }
+    }
+
+    // The path to the day's input, fetching it into the cache if need be
+    pub fn path(&self, day: u32) -> Result<PathBuf, Box<dyn Error>> {
+        if let Some(path) = self.cached(day) {
+            return Ok(path);
+        }
+        let input = self.site.request(
+            &format!("day/{}/input", day),
+            None,
+            &format!("fetch the input for day {} as it isn't cached", day),
+        )?;
+
+        let path = self.cache_path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, input)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::{serve, site, temp_dir};
+
+    #[test]
+    fn fetches_on_a_miss_then_uses_the_cache() {
+        let cache = temp_dir("fetch-miss");
+        let (addr, server) = serve(vec![(200, "1000\n2000\n")]);
+        let site = site(&addr, Some("abc123"));
+        let inputs = Inputs::new(&cache, &site);
+
+        let path = inputs.path(1).unwrap();
+        assert_eq!(path, cache.join("day1").join("src").join("input.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        // The server has stopped by now, so this must come from the cache
+        assert_eq!(inputs.path(1).unwrap(), path);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1"));
+        assert!(requests[0]
+            .to_lowercase()
+            .contains("cookie: session=abc123"));
+    }
+
+    #[test]
+    fn empty_inputs_are_misses() {
+        let cache = temp_dir("fetch-empty");
+        fs::create_dir_all(cache.join("day2").join("src")).unwrap();
+        fs::write(cache.join("day2").join("src").join("input.txt"), "").unwrap();
+        let (addr, server) = serve(vec![(200, "A Y\n")]);
+        let site = site(&addr, Some("abc123"));
+        let inputs = Inputs::new(&cache, &site);
+
+        assert_eq!(inputs.cached(2), None);
+        assert_eq!(
+            fs::read_to_string(inputs.path(2).unwrap()).unwrap(),
+            "A Y\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn needs_a_session_on_a_miss() {
+        let cache = temp_dir("fetch-session");
+        let site = site("http://127.0.0.1:1", None);
+        let inputs = Inputs::new(&cache, &site);
+        let err = inputs.path(3).unwrap_err();
+        assert!(err.to_string().contains("AOC_SESSION"));
+    }
+
+    #[test]
+    fn reports_errors_without_caching() {
+        let cache = temp_dir("fetch-error");
+        let (addr, server) = serve(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let site = site(&addr, Some("abc123"));
+        let inputs = Inputs::new(&cache, &site);
+
+        let err = inputs.path(25).unwrap_err().to_string();
+        assert!(err.contains("404"));
+        assert!(err.contains("before it unlocks"));
+        assert_eq!(inputs.cached(25), None);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let cache = temp_dir("fetch-rate");
+        let (addr, server) = serve(vec![(200, "1\n"), (200, "2\n"), (200, "3\n")]);
+        let site = site(&addr, Some("abc123"));
+        let inputs = Inputs::new(&cache, &site);
+
+        let start = Instant::now();
+        for day in 1..=3 {
+            inputs.path(day).unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        server.join().unwrap();
+    }
+}
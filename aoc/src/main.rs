@@ -0,0 +1,608 @@
+use std::{
+    any::Any,
+    error::Error,
+    fmt,
+    fs::{self, File},
+    io::{self, BufWriter, IsTerminal},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    diagnostics, error, input,
+    solution::UnimplementedPartError,
+    visual::{self, FrameWriter, Sink},
+    Part, Solution,
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+use tracing::info_span;
+use tracing_subscriber::{filter::filter_fn, fmt::format::FmtSpan, prelude::*, EnvFilter};
+
+mod answers;
+mod bench;
+mod fetch;
+mod registry;
+mod scaffold;
+#[cfg(test)]
+mod stub_server;
+mod submit;
+mod verify;
+mod watch;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    fetch: FetchArgs,
+    /// Log more about what's going on: -v for how long each phase takes, -vv
+    /// for what the solutions log as they go and -vvv for every step. RUST_LOG
+    /// can pick out more precisely what to log instead
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+// Send logs to stderr, so the answers on stdout are left alone. Logs while
+// diagnostics are being captured only go to the capture.
+fn init_logging(verbose: u8) {
+    let filter = match verbose {
+        0 => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        1 => EnvFilter::new("info"),
+        2 => EnvFilter::new("debug"),
+        _ => EnvFilter::new("trace"),
+    };
+    let output = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(filter_fn(|_| !diagnostics::capturing()));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(output)
+        .with(diagnostics::CaptureLayer)
+        .init();
+}
+
+// Where puzzle inputs are cached, and how to fetch the ones that aren't
+#[derive(Args)]
+struct FetchArgs {
+    /// Where inputs are cached, as dayN/src/input.txt
+    #[arg(long, global = true, env = "AOC_CACHE_DIR", default_value = WORKSPACE_ROOT)]
+    cache: PathBuf,
+    /// The year's page on the site, which inputs are fetched from
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_BASE_URL",
+        default_value = "https://adventofcode.com/2022"
+    )]
+    base_url: String,
+    /// The session cookie from logging in to the site, to fetch inputs with
+    #[arg(long, global = true, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+// Leave this long between requests to the site, to go easy on it
+const FETCH_INTERVAL: Duration = Duration::from_secs(5);
+
+// The most elves `calories --top` will list, which is already far more than
+// anyone would read
+const MAX_TOP: i64 = 1_000_000;
+
+impl FetchArgs {
+    fn site(&self) -> fetch::Site {
+        let config = fetch::SiteConfig {
+            base_url: self.base_url.clone(),
+            session: self.session.clone(),
+            min_interval: FETCH_INTERVAL,
+        };
+        fetch::Site::new(config, Box::new(fetch::Ureq::new()))
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver for a day on the given input
+    Run {
+        /// The day to run
+        #[arg(long)]
+        day: u32,
+        /// The part to run. Runs every implemented part if not given
+        #[arg(long)]
+        part: Option<Part>,
+        /// Path to the puzzle input, or `-` for stdin. If not given, uses the
+        /// day's cached input, fetching it if need be
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[command(flatten)]
+        watch: WatchArgs,
+    },
+    /// Look into what the elves in day 1 are carrying
+    Calories {
+        /// Path to the puzzle input, read the same way as for `run`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Report the elves carrying the most calories, and how many that is
+        /// altogether. 1 and 3 give the two parts' answers
+        #[arg(
+            long,
+            short,
+            default_value_t = 3,
+            value_parser = clap::value_parser!(u32).range(1..=MAX_TOP)
+        )]
+        top: u32,
+        /// Report what each elf is carrying, the median total and a histogram
+        /// of the totals instead
+        #[arg(long, conflicts_with = "top")]
+        stats: bool,
+        /// Read the input file in chunks on this many threads, for huge
+        /// inputs. Stdin is always read on one thread
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        threads: u32,
+    },
+    /// Score day 2's strategy guide playing a different game
+    Game {
+        /// The game, as TOML listing the moves and what each beats. See
+        /// day2/src/rpsls.toml
+        #[arg(long)]
+        game: PathBuf,
+        /// Path to the strategy guide, read the same way as for `run`
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// List the days and parts that are implemented
+    List,
+    /// Make sure the input for a day, or every day, is cached, and print where
+    Fetch {
+        /// Only fetch this day
+        #[arg(long)]
+        day: Option<u32>,
+    },
+    /// Solve a part and submit the answer, unless it's known to be wrong
+    Submit {
+        /// The day to submit
+        #[arg(long)]
+        day: u32,
+        /// The part to submit
+        #[arg(long)]
+        part: Part,
+        /// Submit this answer instead of solving the day's input
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Check every day's answers against those recorded in its answers.toml
+    Verify {
+        /// The top of the workspace, containing a dayN directory for each day
+        #[arg(long, default_value = WORKSPACE_ROOT)]
+        root: PathBuf,
+    },
+    /// Time the parse and each part of every day on its input
+    Bench {
+        /// Only benchmark this day
+        #[arg(long)]
+        day: Option<u32>,
+        /// How many times to run each phase
+        #[arg(long, short = 'n', default_value_t = 10)]
+        iterations: usize,
+        /// Write the timings to this CSV file
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Compare against the timings in a CSV file from an earlier run
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+    },
+    /// Create a crate for a new day and add it to the workspace and the runner
+    New {
+        /// The day to create
+        #[arg(long)]
+        day: u32,
+        /// The top of the workspace, where the dayN directory is created
+        #[arg(long, default_value = WORKSPACE_ROOT)]
+        root: PathBuf,
+    },
+}
+
+// How to show the frames of the days that draw them
+#[derive(Args)]
+struct WatchArgs {
+    /// Watch the days that draw what they're doing in the terminal
+    #[arg(long, conflicts_with_all = ["frames", "format"])]
+    watch: bool,
+    /// How many frames a second to start watching at, up to 1000
+    #[arg(
+        long,
+        default_value_t = 30,
+        requires = "watch",
+        value_parser = clap::value_parser!(u32).range(1..=i64::from(watch::MAX_FPS))
+    )]
+    fps: u32,
+    /// Write every frame to this file as text instead
+    #[arg(long, conflicts_with = "format")]
+    frames: Option<PathBuf>,
+}
+
+impl WatchArgs {
+    fn sink(&self) -> Result<Option<Box<dyn Sink>>, Box<dyn Error>> {
+        if self.watch {
+            return Ok(Some(Box::new(watch::Terminal::new(self.fps)?)));
+        }
+        match &self.frames {
+            Some(path) => {
+                let file = File::create(path).map_err(|err| error::in_file(Box::new(err), path))?;
+                Ok(Some(Box::new(FrameWriter(BufWriter::new(file)))))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// Where the days' inputs and answers are found by default
+const WORKSPACE_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Answers for people to read, with any logs going to stderr
+    Text,
+    /// A single JSON object with each part's answer, timing and anything
+    /// logged at the chosen verbosity
+    Json,
+}
+
+#[derive(Debug, Clone)]
+struct UnknownDayError(u32);
+
+impl Error for UnknownDayError {}
+
+impl fmt::Display for UnknownDayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Day {} is not implemented, see `aoc list`", self.0)
+    }
+}
+
+// Where to read the day's input from, or None for stdin
+fn day_input_path(
+    day: u32,
+    path: Option<PathBuf>,
+    inputs: &fetch::Inputs,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    match path {
+        // Only read stdin when asked to, so that running under a pipe or cron
+        // doesn't quietly solve whatever happens to be on it
+        None => Ok(Some(inputs.path(day)?)),
+        path if input::is_stdin(path.as_ref()) => Ok(None),
+        path => Ok(path),
+    }
+}
+
+// The name to give the input in errors, even if it came from stdin
+fn input_name(path: Option<&Path>) -> &Path {
+    path.unwrap_or(Path::new("<stdin>"))
+}
+
+// Read the day's input, returning it along with a name for it to use in
+// errors
+fn read_day_input(
+    day: u32,
+    path: Option<PathBuf>,
+    inputs: &fetch::Inputs,
+) -> Result<(String, PathBuf), Box<dyn Error>> {
+    let path = day_input_path(day, path, inputs)?;
+    let input = input::read_input(path.as_ref())?;
+    Ok((input, input_name(path.as_deref()).to_path_buf()))
+}
+
+// Parse the input in the file at `path`, or stdin if there isn't one. Files
+// are left to the solver to read, as some can read huge ones quicker than
+// reading them in and then parsing them.
+fn parse_input(
+    solver: &dyn aoc_common::Solver,
+    path: Option<&Path>,
+) -> Result<Box<dyn Any>, Box<dyn Error>> {
+    let parsed = match path {
+        Some(path) => solver.parse_file(path),
+        None => solver.parse(&input::read_all(io::stdin().lock())?),
+    };
+    parsed.map_err(|err| error::in_file(err, input_name(path)))
+}
+
+fn run(
+    day: u32,
+    part: Option<Part>,
+    path: Option<PathBuf>,
+    format: Format,
+    watch: WatchArgs,
+    inputs: &fetch::Inputs,
+) -> Result<(), Box<dyn Error>> {
+    let entry = registry::find(day).ok_or(UnknownDayError(day))?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => entry.solver.parts().to_vec(),
+    };
+
+    let path = day_input_path(day, path, inputs)?;
+    let parse = || parse_input(entry.solver, path.as_deref());
+    if format == Format::Json {
+        return run_json(entry, &parts, parse);
+    }
+    if parts.is_empty() {
+        println!("Day {}: nothing yet", day);
+        return Ok(());
+    }
+
+    let solve = || -> Result<Vec<(Part, String)>, Box<dyn Error>> {
+        let parsed = info_span!("parse", day).in_scope(parse)?;
+        let mut answers = vec![];
+        for part in parts {
+            let answer = info_span!("solve", day, part = part.number())
+                .in_scope(|| entry.solver.solve(parsed.as_ref(), part))?;
+            answers.push((part, answer));
+        }
+        Ok(answers)
+    };
+    // Any terminal being watched has to be put back before the answers show
+    let answers = match watch.sink()? {
+        Some(sink) => visual::watch(sink, solve)?,
+        None => solve()?,
+    };
+    for (part, answer) in answers {
+        // Some answers are pictures, which need to start on their own line
+        if answer.contains('\n') {
+            println!("Day {} part {}:\n{}", day, part, answer);
+        } else {
+            println!("Day {} part {}: {}", day, part, answer);
+        }
+    }
+    Ok(())
+}
+
+fn calories(
+    path: Option<PathBuf>,
+    top: u32,
+    stats: bool,
+    threads: u32,
+    inputs: &fetch::Inputs,
+) -> Result<(), Box<dyn Error>> {
+    let path = day_input_path(1, path, inputs)?;
+    let named = |err| error::in_file(err, input_name(path.as_deref()));
+    let (top, threads) = (top as usize, threads as usize);
+    let elves = match path.as_deref() {
+        // Only the top elves are kept, so a huge file needn't fit in memory
+        Some(file) if !stats => {
+            let top = day1::top_k_file(file, top, threads).map_err(named)?;
+            println!("{}", day1::top_k_report(&top));
+            return Ok(());
+        }
+        Some(file) => day1::read_elves(file, threads).map_err(named)?,
+        // Stdin can only be read from the start, so it's read on one thread
+        None => day1::Day1
+            .parse(&input::read_all(io::stdin().lock())?)
+            .map_err(named)?,
+    };
+    if stats {
+        print!("{}", day1::stats_report(&elves));
+    } else {
+        println!("{}", day1::top_k_report(&day1::top_k(&elves, top)));
+    }
+    Ok(())
+}
+
+fn game(
+    file: PathBuf,
+    path: Option<PathBuf>,
+    inputs: &fetch::Inputs,
+) -> Result<(), Box<dyn Error>> {
+    // Neither error knows which file it's about
+    let named = |err: &dyn fmt::Display| format!("{}: {}", file.display(), err);
+    let contents = fs::read_to_string(&file).map_err(|err| named(&err))?;
+    let game = day2::Game::from_toml(&contents).map_err(|err| named(&err))?;
+    let (input, path) = read_day_input(2, path, inputs)?;
+    // Each part reads the second column differently, so a guide can be fine
+    // for part 1 but not part 2
+    let moves = game.parse_moves(&input).map_err(|err| err.in_file(&path))?;
+    println!("Playing the moves: {}", game.score_moves(&moves));
+    let results = game
+        .parse_results(&input)
+        .map_err(|err| err.in_file(&path))?;
+    println!("Getting the results: {}", game.score_results(&results));
+    Ok(())
+}
+
+// Time how long `f` takes, capturing any diagnostics it emits
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration, Vec<String>) {
+    let start = Instant::now();
+    let (res, diagnostics) = diagnostics::capture(f);
+    (res, start.elapsed(), diagnostics)
+}
+
+// Describes one phase of the run, leaving out the diagnostics if there were none
+fn phase_json(time: Duration, diagnostics: Vec<String>) -> serde_json::Map<String, Value> {
+    let mut phase = serde_json::Map::new();
+    phase.insert(String::from("time_ns"), json!(time.as_nanos() as u64));
+    if !diagnostics.is_empty() {
+        phase.insert(String::from("diagnostics"), json!(diagnostics));
+    }
+    phase
+}
+
+fn run_json<P>(entry: &registry::Day, parts: &[Part], parse: P) -> Result<(), Box<dyn Error>>
+where
+    P: FnOnce() -> Result<Box<dyn Any>, Box<dyn Error>>,
+{
+    let (parsed, time, diagnostics) = timed(parse);
+    let parsed = parsed?;
+    let parse = phase_json(time, diagnostics);
+
+    let mut results = vec![];
+    for &part in parts {
+        let (answer, time, diagnostics) = timed(|| entry.solver.solve(parsed.as_ref(), part));
+        let mut result = serde_json::Map::new();
+        result.insert(String::from("part"), json!(part.number()));
+        result.insert(String::from("answer"), json!(answer?));
+        result.extend(phase_json(time, diagnostics));
+        results.push(Value::Object(result));
+    }
+
+    let output = json!({
+        "day": entry.day,
+        "parse": parse,
+        "parts": results,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+fn fetch(day: Option<u32>, inputs: &fetch::Inputs) -> Result<(), Box<dyn Error>> {
+    if let Some(day) = day {
+        registry::find(day).ok_or(UnknownDayError(day))?;
+    }
+    for entry in registry::DAYS {
+        if day.is_none_or(|day| day == entry.day) {
+            println!(
+                "Day {:>2}: {}",
+                entry.day,
+                inputs.path(entry.day)?.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn submit(
+    day: u32,
+    part: Part,
+    answer: Option<String>,
+    inputs: &fetch::Inputs,
+    site: &fetch::Site,
+) -> Result<(), Box<dyn Error>> {
+    let entry = registry::find(day).ok_or(UnknownDayError(day))?;
+    // Even with --answer, so nothing gets sent for a part that isn't solved
+    if !entry.solver.parts().contains(&part) {
+        return Err(Box::new(UnimplementedPartError(part)));
+    }
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let path = inputs.path(day)?;
+            let input = fs::read_to_string(&path)?;
+            let parsed = entry
+                .solver
+                .parse(&input)
+                .map_err(|err| error::in_file(err, &path))?;
+            entry.solver.solve(parsed.as_ref(), part)?
+        }
+    };
+
+    let path = inputs.day_dir(day).join("submissions.toml");
+    let mut history = submit::History::load(&path).map_err(|err| error::in_file(err, &path))?;
+    println!("Day {} part {}: submitting {}", day, part, answer);
+    let verdict = submit::submit(site, &mut history, day, part, &answer)?;
+    println!("{}", verdict);
+    Ok(())
+}
+
+fn bench(
+    day: Option<u32>,
+    iterations: usize,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    inputs: &fetch::Inputs,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(day) = day {
+        registry::find(day).ok_or(UnknownDayError(day))?;
+    }
+    bench::bench(
+        inputs,
+        day,
+        iterations,
+        output.as_deref(),
+        baseline.as_deref(),
+    )
+}
+
+fn list() {
+    for entry in registry::DAYS {
+        let parts: Vec<String> = entry
+            .solver
+            .parts()
+            .iter()
+            .map(|part| part.to_string())
+            .collect();
+        if parts.is_empty() {
+            println!("Day {:>2}: nothing yet", entry.day);
+        } else {
+            println!("Day {:>2}: part {}", entry.day, parts.join(", "));
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    let site = cli.fetch.site();
+    let inputs = fetch::Inputs::new(&cli.fetch.cache, &site);
+    let res = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            watch,
+        } => run(day, part, input, format, watch, &inputs),
+        Command::Calories {
+            input,
+            top,
+            stats,
+            threads,
+        } => calories(input, top, stats, threads, &inputs),
+        Command::Game { game: file, input } => game(file, input, &inputs),
+        Command::List => {
+            list();
+            Ok(())
+        }
+        Command::Fetch { day } => fetch(day, &inputs),
+        Command::Submit { day, part, answer } => submit(day, part, answer, &inputs, &site),
+        Command::Verify { root } => verify::verify(&root, &inputs),
+        Command::Bench {
+            day,
+            iterations,
+            output,
+            baseline,
+        } => bench(day, iterations, output, baseline, &inputs),
+        Command::New { day, root } => scaffold::new_day(&root, day),
+    };
+    if let Err(err) = res {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_has_to_be_sensible() {
+        let parse = |top: &str| Cli::try_parse_from(["aoc", "calories", "--top", top]);
+        assert!(parse("5").is_ok());
+        for top in ["0", "1000001", "100000000000"] {
+            assert!(parse(top).is_err(), "{}", top);
+        }
+    }
+
+    #[test]
+    fn fps_has_to_be_sensible() {
+        let parse =
+            |fps: &str| Cli::try_parse_from(["aoc", "run", "--day", "14", "--watch", "--fps", fps]);
+        assert!(parse("1000").is_ok());
+        for fps in ["0", "1001", "4294967295"] {
+            assert!(parse(fps).is_err(), "{}", fps);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use aoc_common::Solver;
+
+/// Everything the runner needs to know about a day's solver.
+pub struct Day {
+    pub day: u32,
+    pub solver: &'static dyn Solver,
+}
+
+/// Every implemented day, in order. Add new days here.
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        solver: &day1::Day1,
+    },
+    Day {
+        day: 2,
+        solver: &day2::Day2,
+    },
+    Day {
+        day: 3,
+        solver: &day3::Day3,
+    },
+    Day {
+        day: 4,
+        solver: &day4::Day4,
+    },
+    Day {
+        day: 5,
+        solver: &day5::Day5,
+    },
+    Day {
+        day: 6,
+        solver: &day6::Day6,
+    },
+    Day {
+        day: 7,
+        solver: &day7::Day7,
+    },
+    Day {
+        day: 8,
+        solver: &day8::Day8,
+    },
+    Day {
+        day: 9,
+        solver: &day9::Day9,
+    },
+    Day {
+        day: 10,
+        solver: &day10::Day10,
+    },
+    Day {
+        day: 11,
+        solver: &day11::Day11,
+    },
+    Day {
+        day: 12,
+        solver: &day12::Day12,
+    },
+    Day {
+        day: 13,
+        solver: &day13::Day13,
+    },
+    Day {
+        day: 14,
+        solver: &day14::Day14,
+    },
+    Day {
+        day: 15,
+        solver: &day15::Day15,
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
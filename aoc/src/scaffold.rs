@@ -0,0 +1,403 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub struct ScaffoldError(String);
+
+impl Error for ScaffoldError {}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+const MANIFEST: &str = r#"[package]
+name = "day{N}"
+version.workspace = true
+edition.workspace = true
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common.workspace = true
+"#;
+
+const LIB: &str = r#"use std::error::Error;
+
+use aoc_common::{solution::UnimplementedPartError, Part, Solution};
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    // One string per line of the input, until there's something better
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    // Add each part here once it's solved, so that nothing runs or submits it
+    // before then
+    const PARTS: &'static [Part] = &[];
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Err(Box::new(UnimplementedPartError(Part::One)))
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Err(Box::new(UnimplementedPartError(Part::Two)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn solves_example() {
+        // Add the example's answers from the puzzle as each part is solved
+        let answers = solve_all(&Day{N}, include_str!("example.txt")).unwrap();
+        assert_eq!(answers, Vec::<String>::new());
+    }
+}
+"#;
+
+const ANSWERS: &str = r#"# Known answers for each input, checked by `aoc verify`
+
+[example]
+# part1 =
+# part2 =
+
+[input]
+# part1 =
+# part2 =
+"#;
+
+// The day number in a name like "day12"
+fn day_of(name: &str) -> Option<u32> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+// Insert `new` before the first line that `day_of_line` says belongs to a day
+// after `day`, so the days stay in order, or at `fallback` if there isn't one.
+fn insert_in_order<F>(lines: &mut Vec<String>, new: &str, day: u32, fallback: usize, day_of_line: F)
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let idx = lines
+        .iter()
+        .position(|line| day_of_line(line).is_some_and(|other| other > day))
+        .unwrap_or(fallback);
+    lines.splice(idx..idx, new.lines().map(String::from));
+}
+
+fn to_lines(text: &str) -> Vec<String> {
+    text.lines().map(String::from).collect()
+}
+
+fn from_lines(lines: &[String]) -> String {
+    lines.join("\n") + "\n"
+}
+
+// Add `dayN` to the members of the workspace manifest
+fn add_member(manifest: &str, day: u32) -> Result<String, ScaffoldError> {
+    let mut lines = to_lines(manifest);
+    let member = format!("    \"day{}\",", day);
+    let start = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("members = ["));
+    let end = start.and_then(|start| {
+        lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .map(|idx| start + idx)
+    });
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ScaffoldError(String::from(
+            "Couldn't find the workspace members in Cargo.toml",
+        )));
+    };
+    // A stub being replaced is already a member
+    if lines[start..end]
+        .iter()
+        .any(|line| line.trim() == member.trim())
+    {
+        return Ok(from_lines(&lines));
+    }
+    let mut members = lines.split_off(start + 1);
+    let rest = members.split_off(end - start - 1);
+    insert_in_order(&mut members, &member, day, end - start - 1, |line| {
+        day_of(line.trim().trim_end_matches(',').trim_matches('"'))
+    });
+    lines.extend(members);
+    lines.extend(rest);
+    Ok(from_lines(&lines))
+}
+
+// Make the runner depend on the new day, keeping the days in order within its
+// [dependencies]
+fn add_dependency(manifest: &str, day: u32) -> Result<String, ScaffoldError> {
+    let mut lines = to_lines(manifest);
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+    else {
+        return Err(ScaffoldError(String::from(
+            "Couldn't find the runner's [dependencies] in aoc/Cargo.toml",
+        )));
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |idx| start + 1 + idx);
+    let line_day = |line: &str| day_of(line.split(' ').next()?);
+    let mut table = lines.split_off(start + 1);
+    let rest = table.split_off(end - start - 1);
+    if table.iter().any(|line| line_day(line) == Some(day)) {
+        return Ok(manifest.to_string());
+    }
+    // Without a later day, it goes after the last one, or failing that at
+    // the end of the table, before any blank lines leading to the next
+    let fallback = table
+        .iter()
+        .rposition(|line| line_day(line).is_some())
+        .or_else(|| table.iter().rposition(|line| !line.trim().is_empty()))
+        .map_or(0, |idx| idx + 1);
+    insert_in_order(
+        &mut table,
+        &format!("day{0} = {{ path = \"../day{0}\" }}", day),
+        day,
+        fallback,
+        line_day,
+    );
+    lines.extend(table);
+    lines.extend(rest);
+    Ok(from_lines(&lines))
+}
+
+// Add the new day to the runner's table of days
+fn add_to_registry(registry: &str, day: u32) -> Result<String, ScaffoldError> {
+    let mut lines = to_lines(registry);
+    if registry.contains(&format!("solver: &day{0}::Day{0},", day)) {
+        return Ok(registry.to_string());
+    }
+    let Some(end) = lines.iter().position(|line| line == "];") else {
+        return Err(ScaffoldError(String::from(
+            "Couldn't find the end of DAYS in the registry",
+        )));
+    };
+    // Each day's entry starts the line before its number
+    let entry = format!(
+        "    Day {{\n        day: {0},\n        solver: &day{0}::Day{0},\n    }},",
+        day
+    );
+    let idx = lines
+        .iter()
+        .position(|line| {
+            line.trim()
+                .strip_prefix("day: ")
+                .and_then(|n| n.trim_end_matches(',').parse::<u32>().ok())
+                .is_some_and(|other| other > day)
+        })
+        .map_or(end, |idx| idx - 1);
+    lines.splice(idx..idx, entry.lines().map(String::from));
+    Ok(from_lines(&lines))
+}
+
+// Add the new day to the editor's list of folders, keeping the tab indents
+fn add_folder(workspace: &str, day: u32) -> Result<String, Box<dyn Error>> {
+    let mut json: Value = serde_json::from_str(workspace)?;
+    let Some(folders) = json.get_mut("folders").and_then(Value::as_array_mut) else {
+        return Err(Box::new(ScaffoldError(String::from(
+            "Couldn't find the folders in the .code-workspace",
+        ))));
+    };
+    let name = format!("day{}", day);
+    if folders.iter().any(|folder| folder["path"] == name.as_str()) {
+        return Ok(workspace.to_string());
+    }
+    let idx = folders
+        .iter()
+        .position(|folder| {
+            folder["path"]
+                .as_str()
+                .and_then(day_of)
+                .is_some_and(|other| other > day)
+        })
+        .unwrap_or(folders.len());
+    folders.insert(idx, serde_json::json!({ "path": name }));
+
+    // serde_json indents with two spaces, but the editor wrote tabs
+    let pretty = serde_json::to_string_pretty(&json)?;
+    let lines: Vec<String> = pretty
+        .lines()
+        .map(|line| {
+            let body = line.trim_start_matches(' ');
+            "\t".repeat((line.len() - body.len()) / 2) + body
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+const STUB_MAIN: &str = "fn main() {\n    println!(\"Hello, world!\");\n}\n";
+
+// Whether `dir` is only what `cargo new` made, plus maybe the input. Those can
+// be replaced with the real thing, keeping the input.
+fn is_stub(dir: &Path) -> bool {
+    let files = ["Cargo.toml", "src/main.rs", "src/input.txt"];
+    let only_stub_files = |dir: &Path, prefix: &str| -> io::Result<bool> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            if !files.contains(&name.as_str()) && name != "src" {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+    only_stub_files(dir, "").unwrap_or(false)
+        && only_stub_files(&dir.join("src"), "src/").unwrap_or(false)
+        && fs::read_to_string(dir.join("src").join("main.rs")).is_ok_and(|main| main == STUB_MAIN)
+}
+
+// Create a crate for a new day under `root`, the top of the workspace, and
+// hook it up to the workspace, the editor and the runner.
+pub fn new_day(root: &Path, day: u32) -> Result<(), Box<dyn Error>> {
+    if !(1..=25).contains(&day) {
+        return Err(Box::new(ScaffoldError(format!(
+            "There is no day {} of advent",
+            day
+        ))));
+    }
+    let dir = root.join(format!("day{}", day));
+    let stub = is_stub(&dir);
+    if dir.exists() && !stub {
+        return Err(Box::new(ScaffoldError(format!(
+            "{} already exists",
+            dir.display()
+        ))));
+    }
+
+    // Work out all the edits first, so nothing is half done if one fails
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_member(&fs::read_to_string(&manifest_path)?, day)?;
+    let runner_path = root.join("aoc").join("Cargo.toml");
+    let runner = add_dependency(&fs::read_to_string(&runner_path)?, day)?;
+    let registry_path = root.join("aoc").join("src").join("registry.rs");
+    let registry = add_to_registry(&fs::read_to_string(&registry_path)?, day)?;
+    let mut code_workspace = None;
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "code-workspace") {
+            let folders = add_folder(&fs::read_to_string(&path)?, day)?;
+            code_workspace = Some((path, folders));
+        }
+    }
+
+    let fill = |template: &str| template.replace("{N}", &day.to_string());
+    fs::create_dir_all(dir.join("src"))?;
+    if stub {
+        fs::remove_file(dir.join("src").join("main.rs"))?;
+    }
+    fs::write(dir.join("Cargo.toml"), fill(MANIFEST))?;
+    fs::write(dir.join("src").join("lib.rs"), fill(LIB))?;
+    fs::write(dir.join("src").join("example.txt"), "")?;
+    let input = dir.join("src").join("input.txt");
+    if !input.exists() {
+        fs::write(input, "")?;
+    }
+    fs::write(dir.join("answers.toml"), ANSWERS)?;
+
+    fs::write(manifest_path, manifest)?;
+    fs::write(runner_path, runner)?;
+    fs::write(registry_path, registry)?;
+    if let Some((path, folders)) = code_workspace {
+        fs::write(path, folders)?;
+    }
+
+    println!("Created {}", dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_days_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day3\",\n]\n";
+        assert_eq!(
+            add_member(manifest, 2).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n    \"day3\",\n]\n"
+        );
+        assert!(add_member(manifest, 4)
+            .unwrap()
+            .ends_with("\"day3\",\n    \"day4\",\n]\n"));
+
+        let runner = "[dependencies]\nday1 = { path = \"../day1\" }\n";
+        assert!(add_dependency(runner, 2)
+            .unwrap()
+            .ends_with("day2 = { path = \"../day2\" }\n"));
+
+        let registry = "pub const DAYS: &[Day] = &[\n    Day {\n        day: 3,\n        solver: &day3::Day3,\n    },\n];\n";
+        let registry = add_to_registry(registry, 2).unwrap();
+        assert!(registry.starts_with("pub const DAYS: &[Day] = &[\n    Day {\n        day: 2,"));
+    }
+
+    #[test]
+    fn adds_dependencies_to_their_own_table() {
+        let runner = "[dependencies]\nclap = \"4\"\nday1 = { path = \"../day1\" }\n\n[dev-dependencies]\nproptest = \"1\"\n";
+        assert_eq!(
+            add_dependency(runner, 2).unwrap(),
+            "[dependencies]\nclap = \"4\"\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n\n[dev-dependencies]\nproptest = \"1\"\n"
+        );
+        let runner = "[dependencies]\nclap = \"4\"\n\n[features]\n";
+        assert_eq!(
+            add_dependency(runner, 1).unwrap(),
+            "[dependencies]\nclap = \"4\"\nday1 = { path = \"../day1\" }\n\n[features]\n"
+        );
+        assert!(add_dependency("[package]\n", 1).is_err());
+    }
+
+    #[test]
+    fn leaves_days_that_are_already_there() {
+        let manifest = "[workspace]\nmembers = [\n    \"day1\",\n]\n";
+        assert_eq!(add_member(manifest, 1).unwrap(), manifest);
+        let runner = "[dependencies]\nday1 = { path = \"../day1\" }\n";
+        assert_eq!(add_dependency(runner, 1).unwrap(), runner);
+        let workspace = "{\n\t\"folders\": [\n\t\t{\n\t\t\t\"path\": \"day1\"\n\t\t}\n\t]\n}";
+        assert_eq!(add_folder(workspace, 1).unwrap(), workspace);
+    }
+
+    #[test]
+    fn replaces_a_stub() {
+        let root = crate::stub_server::temp_dir("scaffold-stub");
+        let dir = root.join("day15");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(dir.join("src").join("main.rs"), STUB_MAIN).unwrap();
+        fs::write(dir.join("src").join("input.txt"), "puzzle").unwrap();
+        assert!(is_stub(&dir));
+
+        fs::write(dir.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+        assert!(!is_stub(&dir));
+        fs::write(dir.join("src").join("main.rs"), STUB_MAIN).unwrap();
+        fs::write(dir.join("src").join("lib.rs"), "").unwrap();
+        assert!(!is_stub(&dir));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_code_workspace_layout() {
+        let workspace = "{\n\t\"folders\": [\n\t\t{\n\t\t\t\"path\": \"day1\"\n\t\t}\n\t],\n\t\"settings\": {}\n}";
+        assert_eq!(
+            add_folder(workspace, 2).unwrap(),
+            "{\n\t\"folders\": [\n\t\t{\n\t\t\t\"path\": \"day1\"\n\t\t},\n\t\t{\n\t\t\t\"path\": \"day2\"\n\t\t}\n\t],\n\t\"settings\": {}\n}"
+        );
+    }
+}
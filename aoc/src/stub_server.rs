@@ -0,0 +1,74 @@
+// Test helpers for talking to the site without the site
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    process, thread,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::fetch::{Site, SiteConfig, Ureq};
+
+// A one-shot HTTP server on localhost, giving each response in turn to a
+// request and then stopping. Returns its address and the requests it got.
+pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = vec![];
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                request += &line;
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request += &String::from_utf8(content).unwrap();
+            requests.push(request);
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (addr, handle)
+}
+
+// An empty directory to use as the cache
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Talks to a stub server at `base_url` as though it were the site for 2022,
+// without waiting long between requests
+pub fn site(base_url: &str, session: Option<&str>) -> Site {
+    let config = SiteConfig {
+        base_url: format!("{}/2022", base_url),
+        session: session.map(String::from),
+        min_interval: Duration::from_millis(100),
+    };
+    Site::new(config, Box::new(Ureq::new()))
+}
@@ -0,0 +1,409 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::Part;
+
+use crate::fetch::Site;
+
+// What the site made of an answer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // The site didn't look at the answer, so these say nothing about it
+    TooSoon,
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    // How each verdict is written in the history
+    const NAMES: [(Verdict, &'static str); 7] = [
+        (Verdict::Right, "right"),
+        (Verdict::Wrong, "wrong"),
+        (Verdict::TooHigh, "too-high"),
+        (Verdict::TooLow, "too-low"),
+        (Verdict::TooSoon, "too-soon"),
+        (Verdict::WrongLevel, "wrong-level"),
+        (Verdict::Unknown, "unknown"),
+    ];
+
+    fn name(self) -> &'static str {
+        Verdict::NAMES
+            .iter()
+            .find(|(verdict, _)| *verdict == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        Verdict::NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(verdict, _)| *verdict)
+    }
+
+    // Pick the verdict out of the page the site sends back
+    fn from_page(page: &str) -> Verdict {
+        if page.contains("That's the right answer") {
+            Verdict::Right
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "That's the right answer!"),
+            Verdict::Wrong => write!(f, "That's not the right answer"),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Verdict::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Verdict::TooSoon => write!(f, "An answer was given too recently, wait and try again"),
+            Verdict::WrongLevel => write!(f, "That part isn't open, or is already solved"),
+            Verdict::Unknown => write!(f, "Couldn't tell what the site made of that answer"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RefusedError(String);
+
+impl Error for RefusedError {}
+
+impl fmt::Display for RefusedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Not submitting: {}", self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryError(String);
+
+impl Error for HistoryError {}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bad submission history: {}", self.0)
+    }
+}
+
+struct Submission {
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+// Every answer submitted for a day and what the site said about it, kept in
+// a TOML file like
+//
+//   [[submission]]
+//   answer = "24000"
+//   part = 1
+//   verdict = "too-high"
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    // A day that's never had anything submitted just has no history
+    pub fn load(path: &Path) -> Result<History, Box<dyn Error>> {
+        let table: toml::Table = match fs::read_to_string(path) {
+            Ok(contents) => contents.parse()?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => toml::Table::new(),
+            Err(err) => return Err(Box::new(err)),
+        };
+
+        let mut submissions = vec![];
+        let entries = match table.get("submission") {
+            Some(toml::Value::Array(entries)) => entries.as_slice(),
+            Some(_) => {
+                return Err(Box::new(HistoryError(String::from(
+                    "expected [[submission]]",
+                ))))
+            }
+            None => &[],
+        };
+        for entry in entries {
+            let field = |key| {
+                entry
+                    .get(key)
+                    .ok_or_else(|| HistoryError(format!("missing {}", key)))
+            };
+            let part = match field("part")? {
+                toml::Value::Integer(part) => part.to_string().parse()?,
+                part => return Err(Box::new(HistoryError(format!("bad part {}", part)))),
+            };
+            let answer = match field("answer")? {
+                toml::Value::String(answer) => answer.clone(),
+                answer => answer.to_string(),
+            };
+            let verdict = field("verdict")?
+                .as_str()
+                .and_then(Verdict::from_name)
+                .ok_or_else(|| HistoryError(String::from("bad verdict")))?;
+            submissions.push(Submission {
+                part,
+                answer,
+                verdict,
+            });
+        }
+        Ok(History {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        let entries = self
+            .submissions
+            .iter()
+            .map(|s| {
+                let mut entry = toml::Table::new();
+                entry.insert(String::from("part"), s.part.number().into());
+                entry.insert(String::from("answer"), s.answer.clone().into());
+                entry.insert(String::from("verdict"), s.verdict.name().into());
+                toml::Value::Table(entry)
+            })
+            .collect::<Vec<_>>();
+        let mut table = toml::Table::new();
+        table.insert(String::from("submission"), toml::Value::Array(entries));
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, toml::to_string(&table)?)?;
+        Ok(())
+    }
+
+    // Why the answer isn't worth sending, if we already know it's wrong or
+    // the site has already said it's right
+    fn check(&self, part: Part, answer: &str) -> Result<(), RefusedError> {
+        let refuse = |why: String| Err(RefusedError(why));
+        if answer.trim().is_empty() || answer.contains('\n') {
+            return refuse(String::from(
+                "the site wants one line, so read any picture and pass it with --answer",
+            ));
+        }
+
+        let judged = self.submissions.iter().filter(|s| s.part == part);
+        if let Some(right) = judged.clone().find(|s| s.verdict == Verdict::Right) {
+            return refuse(format!(
+                "part {} was already solved with {}",
+                part, right.answer
+            ));
+        }
+        let wrong = |s: &&Submission| {
+            matches!(
+                s.verdict,
+                Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+            )
+        };
+        if judged.clone().filter(wrong).any(|s| s.answer == answer) {
+            return refuse(format!("{} was already wrong", answer));
+        }
+
+        // Only numbers can be too high or too low
+        let Ok(answer) = answer.parse::<i64>() else {
+            return Ok(());
+        };
+        let numbers = |verdict| {
+            judged
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i64>().ok())
+        };
+        if let Some(high) = numbers(Verdict::TooHigh).min() {
+            if answer >= high {
+                return refuse(format!("{} was too high, and {} isn't lower", high, answer));
+            }
+        }
+        if let Some(low) = numbers(Verdict::TooLow).max() {
+            if answer <= low {
+                return refuse(format!("{} was too low, and {} isn't higher", low, answer));
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, part: Part, answer: &str, verdict: Verdict) -> Result<(), Box<dyn Error>> {
+        self.submissions.push(Submission {
+            part,
+            answer: String::from(answer),
+            verdict,
+        });
+        self.save()
+    }
+}
+
+// Send an answer for a day to the site, unless the history says it's not
+// worth it, and record what the site said
+pub fn submit(
+    site: &Site,
+    history: &mut History,
+    day: u32,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict, Box<dyn Error>> {
+    history.check(part, answer)?;
+    let level = part.number().to_string();
+    let page = site.request(
+        &format!("day/{}/answer", day),
+        Some(&[("level", &level), ("answer", answer)]),
+        &format!("submit an answer for day {}", day),
+    )?;
+    let verdict = Verdict::from_page(&page);
+    history.record(part, answer, verdict)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::{serve, site, temp_dir};
+
+    fn history(entries: &[(Part, &str, Verdict)]) -> History {
+        History {
+            path: PathBuf::new(),
+            submissions: entries
+                .iter()
+                .map(|&(part, answer, verdict)| Submission {
+                    part,
+                    answer: String::from(answer),
+                    verdict,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reads_verdicts_from_the_page() {
+        let page = |text| format!("<main><article><p>{}</p></article></main>", text);
+        let cases = [
+            (
+                "That's the right answer!  You are one gold star closer.",
+                Verdict::Right,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait.",
+                Verdict::TooSoon,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                Verdict::WrongLevel,
+            ),
+            ("Something else entirely", Verdict::Unknown),
+        ];
+        for (text, verdict) in cases {
+            assert_eq!(Verdict::from_page(&page(text)), verdict, "{}", text);
+        }
+    }
+
+    #[test]
+    fn posts_answers_and_keeps_the_history() {
+        let dir = temp_dir("submit-post");
+        let path = dir.join("submissions.toml");
+        let (addr, server) = serve(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let site = site(&addr, Some("abc123"));
+
+        let mut history = History::load(&path).unwrap();
+        let verdict = submit(&site, &mut history, 1, Part::One, "75000").unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        let verdict = submit(&site, &mut history, 1, Part::One, "70000").unwrap();
+        assert_eq!(verdict, Verdict::Right);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=1&answer=75000"));
+        assert!(requests[1].ends_with("level=1&answer=70000"));
+
+        let history = History::load(&path).unwrap();
+        let recorded: Vec<_> = history
+            .submissions
+            .iter()
+            .map(|s| (s.part, s.answer.as_str(), s.verdict))
+            .collect();
+        assert_eq!(
+            recorded,
+            [
+                (Part::One, "75000", Verdict::TooHigh),
+                (Part::One, "70000", Verdict::Right)
+            ]
+        );
+    }
+
+    #[test]
+    fn refuses_answers_known_to_be_wrong() {
+        let history = history(&[
+            (Part::One, "abc", Verdict::Wrong),
+            (Part::One, "900", Verdict::TooHigh),
+            (Part::One, "1000", Verdict::TooHigh),
+            (Part::One, "100", Verdict::TooLow),
+            (Part::One, "500", Verdict::TooSoon),
+            (Part::One, "600", Verdict::TooSoon),
+            (Part::One, "600", Verdict::Wrong),
+            (Part::Two, "42", Verdict::Right),
+        ]);
+
+        assert!(history.check(Part::One, "abc").is_err());
+        assert!(history.check(Part::One, "900").is_err());
+        assert!(history.check(Part::One, "950").is_err());
+        assert!(history.check(Part::One, "100").is_err());
+        assert!(history.check(Part::One, "600").is_err());
+        assert!(history.check(Part::One, "-5").is_err());
+        assert!(history.check(Part::One, "line\nline").is_err());
+        assert!(history.check(Part::Two, "43").is_err());
+
+        // Too soon says nothing about the answer
+        assert!(history.check(Part::One, "500").is_ok());
+        assert!(history.check(Part::One, "899").is_ok());
+        assert!(history.check(Part::One, "xyz").is_ok());
+    }
+
+    #[test]
+    fn refused_answers_are_not_sent() {
+        let dir = temp_dir("submit-refused");
+        let mut history = history(&[(Part::One, "7", Verdict::Wrong)]);
+        history.path = dir.join("submissions.toml");
+        // Nothing is listening here, so sending anything would fail differently
+        let site = site("http://127.0.0.1:1", Some("abc123"));
+
+        let err = submit(&site, &mut history, 1, Part::One, "7").unwrap_err();
+        assert!(err.to_string().starts_with("Not submitting"));
+        assert!(!history.path.exists());
+    }
+}
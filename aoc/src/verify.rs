@@ -0,0 +1,155 @@
+use std::{error::Error, fmt, fs, path::Path};
+
+use aoc_common::Part;
+
+use crate::{answers::Answers, fetch::Inputs, registry};
+
+enum Status {
+    Pass,
+    Fail { expected: String, got: String },
+    Missing,
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            // Pictures don't fit in the table, so just flag those
+            Status::Fail { expected, got } if expected.contains('\n') || got.contains('\n') => {
+                write!(f, "FAIL")
+            }
+            Status::Fail { expected, got } => {
+                write!(f, "FAIL (expected {}, got {})", expected, got)
+            }
+            Status::Missing => write!(f, "missing"),
+            Status::Error(err) => write!(f, "ERROR ({})", err),
+        }
+    }
+}
+
+struct Row {
+    day: u32,
+    input: &'static str,
+    part: Part,
+    status: Status,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyFailedError(usize);
+
+impl Error for VerifyFailedError {}
+
+impl fmt::Display for VerifyFailedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} answer(s) did not match", self.0)
+    }
+}
+
+// Parse errors show the bad line of input underneath, which won't fit in the
+// table, so only keep the first line of any error.
+fn first_line(err: &dyn Error) -> String {
+    err.to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+// Run every part of a day on one of its inputs
+fn check_input(
+    entry: &registry::Day,
+    input: &'static str,
+    path: &Path,
+    answers: &Answers,
+) -> Vec<Row> {
+    let row = |part, status| Row {
+        day: entry.day,
+        input,
+        part,
+        status,
+    };
+
+    let parsed = fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|text| entry.solver.parse(&text).map_err(|err| first_line(&*err)));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return entry
+                .solver
+                .parts()
+                .iter()
+                .map(|&part| row(part, Status::Error(err.clone())))
+                .collect()
+        }
+    };
+
+    // An answer for a part the solver doesn't list can't be checked, and
+    // probably means the part was left out of PARTS by mistake
+    let unlisted = Part::ALL
+        .into_iter()
+        .filter(|part| !entry.solver.parts().contains(part))
+        .filter(|&part| answers.get(input, part).is_some())
+        .map(|part| {
+            row(
+                part,
+                Status::Error(String::from("not in the solver's parts")),
+            )
+        });
+
+    entry
+        .solver
+        .parts()
+        .iter()
+        .map(|&part| {
+            let status = match (
+                entry.solver.solve(parsed.as_ref(), part),
+                answers.get(input, part),
+            ) {
+                (Err(err), _) => Status::Error(first_line(&*err)),
+                (Ok(_), None) => Status::Missing,
+                (Ok(got), Some(expected)) if got == expected => Status::Pass,
+                (Ok(got), Some(expected)) => Status::Fail { expected, got },
+            };
+            row(part, status)
+        })
+        .chain(unlisted)
+        .collect()
+}
+
+// Check every day and part against the answers recorded in each day's
+// answers.toml, where `root` is the top of the workspace. The examples live
+// with each day, and the real inputs are only checked if they're cached.
+// Missing answers are reported but don't count as a failure.
+pub fn verify(root: &Path, inputs: &Inputs) -> Result<(), Box<dyn Error>> {
+    let mut rows = vec![];
+    for entry in registry::DAYS {
+        let day_dir = root.join(format!("day{}", entry.day));
+        let answers = Answers::load(day_dir.join("answers.toml"))?;
+        let example = day_dir.join("src").join("example.txt");
+        if example.exists() {
+            rows.extend(check_input(entry, "example", &example, &answers));
+        }
+        if let Some(input) = inputs.cached(entry.day) {
+            rows.extend(check_input(entry, "input", &input, &answers));
+        }
+    }
+
+    println!("{:>3}  {:<8} {:>4}  status", "day", "input", "part");
+    for row in &rows {
+        println!(
+            "{:>3}  {:<8} {:>4}  {}",
+            row.day, row.input, row.part, row.status
+        );
+    }
+
+    let failed = rows
+        .iter()
+        .filter(|row| matches!(row.status, Status::Fail { .. } | Status::Error(_)))
+        .count();
+    if failed > 0 {
+        return Err(Box::new(VerifyFailedError(failed)));
+    }
+    Ok(())
+}
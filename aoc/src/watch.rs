@@ -0,0 +1,130 @@
+use std::{
+    io::{self, Stdout, Write},
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc_common::visual::{Frame, Sink};
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+const KEYS: &str = "space: pause  n: step  +/-: speed  q: stop watching";
+
+// Any faster and the terminal can't keep up anyway
+pub const MAX_FPS: u32 = 1000;
+
+// Shows frames full screen in the terminal, at a steady rate that can be
+// changed with the keyboard, and lets them be paused and stepped through.
+// Frames too big for the terminal are cut down to their top middle.
+pub struct Terminal {
+    out: Stdout,
+    fps: u32,
+    paused: bool,
+}
+
+impl Terminal {
+    pub fn new(fps: u32) -> io::Result<Terminal> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Terminal {
+            out,
+            fps: fps.clamp(1, MAX_FPS),
+            paused: false,
+        })
+    }
+
+    fn draw(&mut self, frame: &Frame) -> io::Result<()> {
+        // Some terminals don't know their size, so guess the usual
+        let (cols, rows) = match terminal::size()? {
+            (0, _) | (_, 0) => (80, 24),
+            size => size,
+        };
+        let (cols, rows) = (cols as usize, rows as usize);
+        // Leave room for the caption and the status line
+        let height = rows.saturating_sub(2);
+        let skip = frame.width().saturating_sub(cols) / 2;
+
+        queue!(self.out, MoveTo(0, 0), Clear(ClearType::All))?;
+        let caption: String = frame.caption.chars().take(cols).collect();
+        write!(self.out, "{}\r\n", caption)?;
+        for line in frame.lines(skip, cols, true).iter().take(height) {
+            write!(self.out, "{}\r\n", line)?;
+        }
+        let status = format!(
+            "{} fps{}  {}",
+            self.fps,
+            if self.paused { " (paused)" } else { "" },
+            KEYS
+        );
+        queue!(self.out, MoveTo(0, rows.saturating_sub(1) as u16))?;
+        write!(
+            self.out,
+            "{}",
+            status.chars().take(cols).collect::<String>()
+        )?;
+        self.out.flush()
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        execute!(self.out, Show, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+impl Sink for Terminal {
+    fn show(&mut self, frame: &Frame) -> io::Result<bool> {
+        self.draw(frame)?;
+        let mut next = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            // While paused, only a key moves things on
+            let wait = if self.paused {
+                Duration::from_secs(3600)
+            } else {
+                next.saturating_duration_since(Instant::now())
+            };
+            if !event::poll(wait)? {
+                if self.paused {
+                    continue;
+                }
+                return Ok(true);
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                // Raw mode swallows Ctrl-C, so it has to be handled here
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.restore()?;
+                    process::exit(130);
+                }
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(true),
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => {
+                    self.fps = self.fps.saturating_mul(2).min(MAX_FPS);
+                    next = Instant::now() + Duration::from_secs(1) / self.fps;
+                }
+                KeyCode::Char('-') | KeyCode::Down => {
+                    self.fps = (self.fps / 2).max(1);
+                    next = Instant::now() + Duration::from_secs(1) / self.fps;
+                }
+                _ => continue,
+            }
+            self.draw(frame)?;
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Times finding the top elves in a big generated input file, from opening the
+// file to having the answer. First by reading it all in and parsing it on one
+// thread, the way `Day1::parse` does, then by reading it in chunks on a few
+// threads. Run with `cargo bench -p day1 [-- ELVES]`.
+//
+// The file was only just written, so it's likely to be in the OS's cache, the
+// same for every run. Reading a file that isn't cached will be slower both ways.
+
+use std::{
+    env, fs,
+    hint::black_box,
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc_common::Solution;
+use day1::{top_k, top_k_file, Day1};
+
+const K: usize = 3;
+const RUNS: usize = 5;
+
+// Made up elves with 1 to 15 items of up to 9999 calories each. The same every
+// time, so runs can be compared.
+fn generate(elves: usize) -> String {
+    let mut seed: u64 = 2022;
+    let mut next = |n: u64| {
+        // Knuth's MMIX LCG, which is plenty random for this
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) % n
+    };
+    let mut input = String::new();
+    for _ in 0..elves {
+        for _ in 0..1 + next(15) {
+            input += &(1 + next(9999)).to_string();
+            input.push('\n');
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// The median time of a few runs of `f`
+fn time<T, F: FnMut() -> T>(mut f: F) -> Duration {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    times[RUNS / 2]
+}
+
+fn main() {
+    // `cargo bench` passes --bench, which isn't the number of elves
+    let elves = env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    let input = generate(elves);
+    let path = env::temp_dir().join(format!("day1-bench-{}.txt", process::id()));
+    fs::write(&path, &input).unwrap();
+    println!(
+        "{} elves, {:.1} MB, top {}",
+        elves,
+        input.len() as f64 / 1e6,
+        K
+    );
+    drop(input);
+
+    let sequential = || top_k(&Day1.parse(&fs::read_to_string(&path).unwrap()).unwrap(), K);
+    let expected = sequential();
+    let baseline = time(sequential);
+    println!("{:>12} {:>10.2?}", "read + parse", baseline);
+
+    for threads in [1, 2, 4, 8] {
+        let parallel = || top_k_file(&path, K, threads).unwrap();
+        assert_eq!(parallel(), expected, "{} threads", threads);
+        let elapsed = time(parallel);
+        println!(
+            "{:>4} threads {:>10.2?} {:>6.2}x",
+            threads,
+            elapsed,
+            baseline.as_secs_f64() / elapsed.as_secs_f64()
+        );
+    }
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,213 @@
+// Reading a huge input file on a few threads at once. The file is split into
+// byte ranges, and each thread reads its own range straight from the file,
+// so nothing has to hold the whole file in memory or read it all on one
+// thread first.
+//
+// A range is moved along to start just after a blank line, which is where an
+// elf's lines start, so every elf is read by exactly one thread. Each thread
+// numbers its elves and lines from 0, and they're moved along by however many
+// the threads before found once they're all done.
+
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    panic,
+    path::Path,
+    str, thread,
+};
+
+use aoc_common::{error::parse_in, ParseError};
+
+use super::Elf;
+
+type ChunkError = Box<dyn Error + Send + Sync>;
+
+// What one thread found in its range
+pub struct Chunk<T> {
+    // The number of the first elf in the range, counting from the start of
+    // the file
+    pub first: usize,
+    // Whatever was kept of the range's elves, which are numbered from 0
+    pub found: T,
+}
+
+// What a thread found before its elves and lines are numbered from the start
+struct Counted<T> {
+    elves: usize,
+    lines: usize,
+    found: T,
+}
+
+// The end of the first blank line that starts at or after `offset`, which is
+// where an elf's lines start, or the end of the file if there isn't one
+fn elf_start<R: BufRead + Seek>(file: &mut R, offset: u64, len: u64) -> io::Result<u64> {
+    if offset == 0 || offset >= len {
+        return Ok(offset.min(len));
+    }
+    // Skip the rest of the line that `offset - 1` is in
+    file.seek(SeekFrom::Start(offset - 1))?;
+    let mut line = vec![];
+    let mut pos = offset - 1 + file.read_until(b'\n', &mut line)? as u64;
+    loop {
+        line.clear();
+        let read = file.read_until(b'\n', &mut line)?;
+        pos += read as u64;
+        if read == 0 || line == b"\n" || line == b"\r\n" {
+            return Ok(pos);
+        }
+    }
+}
+
+// The next line from `reader` without its line ending, the same as
+// `str::lines` gives, or None at the end
+fn next_line<'a, R: BufRead>(reader: &mut R, buf: &'a mut Vec<u8>) -> io::Result<Option<&'a str>> {
+    buf.clear();
+    if reader.read_until(b'\n', buf)? == 0 {
+        return Ok(None);
+    }
+    let line =
+        str::from_utf8(buf).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(Some(match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    }))
+}
+
+// Read the elves whose lines start between `from` and `to` in the file, and
+// pass each one to `keep`
+fn read_range<T, K>(
+    path: &Path,
+    from: u64,
+    to: u64,
+    len: u64,
+    mut found: T,
+    keep: &K,
+) -> Result<Counted<T>, ChunkError>
+where
+    K: Fn(&mut T, Elf),
+{
+    let mut file = BufReader::new(File::open(path)?);
+    let start = elf_start(&mut file, from, len)?;
+    let end = elf_start(&mut file, to, len)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = file.take(end - start);
+
+    let mut buf = vec![];
+    let mut elves = 0;
+    let mut lines = 0;
+    let mut elf = None;
+    while let Some(line) = next_line(&mut reader, &mut buf)? {
+        lines += 1;
+        if line.is_empty() {
+            keep(&mut found, elf.take().unwrap_or(Elf::new(elves)));
+            elves += 1;
+            continue;
+        }
+        let mut line_error = |mut err: ParseError| {
+            // Only the line is to hand, so it's numbered from the start of
+            // the range for now
+            err.line = lines;
+            err
+        };
+        let calories = parse_in(line, line).map_err(&mut line_error)?;
+        elf.get_or_insert(Elf::new(elves))
+            .add(calories)
+            .ok_or_else(|| {
+                line_error(ParseError::new(line, line, "Too many calories for one elf"))
+            })?;
+    }
+    // The last elf in the file needn't be followed by a blank line
+    if let Some(elf) = elf {
+        keep(&mut found, elf);
+        elves += 1;
+    }
+    Ok(Counted {
+        elves,
+        lines,
+        found,
+    })
+}
+
+/// Read the elves in the file at `path` on `threads` threads, each starting
+/// with what `new` makes and passing each of its elves to `keep`. What each
+/// thread kept comes back in the order of the file.
+pub fn read_chunks<T, N, K>(
+    path: &Path,
+    threads: usize,
+    new: N,
+    keep: K,
+) -> Result<Vec<Chunk<T>>, Box<dyn Error>>
+where
+    T: Send,
+    N: Fn() -> T + Sync,
+    K: Fn(&mut T, Elf) + Sync,
+{
+    let len = File::open(path)?.metadata()?.len();
+    let threads = threads.max(1) as u64;
+    let results: Vec<Result<Counted<T>, ChunkError>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|i| {
+                let (from, to) = (len * i / threads, len * (i + 1) / threads);
+                let (new, keep) = (&new, &keep);
+                scope.spawn(move || read_range(path, from, to, len, new(), keep))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect()
+    });
+
+    // The first error in the file is in the first range with one, and all
+    // the ranges before it were read to the end, so their lines are counted
+    let mut chunks = vec![];
+    let (mut first, mut lines) = (0, 0);
+    for result in results {
+        let counted = match result {
+            Ok(counted) => counted,
+            Err(err) => match err.downcast::<ParseError>() {
+                Ok(mut err) => {
+                    err.line += lines;
+                    return Err(err);
+                }
+                Err(err) => return Err(err),
+            },
+        };
+        chunks.push(Chunk {
+            first,
+            found: counted.found,
+        });
+        first += counted.elves;
+        lines += counted.lines;
+    }
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn finds_where_elves_start() {
+        let input = "1\n2\n\n3\n\n\n4\r\n\r\n5";
+        let len = input.len() as u64;
+        let start = |offset| elf_start(&mut Cursor::new(input), offset, len).unwrap();
+        assert_eq!(start(0), 0);
+        // Anywhere in the first elf finds the second
+        assert_eq!([1, 2, 3, 4].map(start), [5; 4]);
+        assert_eq!([5, 6, 7].map(start), [8; 3]);
+        // A blank line on its own is an elf too
+        assert_eq!(start(8), 9);
+        assert_eq!(start(10), 14);
+        // Part way into the last blank line
+        assert_eq!(start(13), len);
+        assert_eq!(start(len + 3), len);
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    error::Error,
+    fmt::Write,
+    fs,
+    path::Path,
+    thread,
+};
+
+use aoc_common::{error::parse_in, ParseError, Solution, TopK};
+use tracing::debug;
+
+mod chunked;
+
+// One elf's food, summed up as we go so the items needn't be kept
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Elf {
+    // Where the elf comes in the input, from 0
+    pub index: usize,
+    pub items: usize,
+    pub total: i32,
+    pub max_item: Option<i32>,
+}
+
+impl Elf {
+    fn new(index: usize) -> Elf {
+        Elf {
+            index,
+            ..Elf::default()
+        }
+    }
+
+    fn add(&mut self, calories: i32) -> Option<()> {
+        self.total = self.total.checked_add(calories)?;
+        self.items += 1;
+        self.max_item = self.max_item.max(Some(calories));
+        Some(())
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.items > 0).then(|| self.total as f64 / self.items as f64)
+    }
+}
+
+// Orders elves by how much they're carrying, with the earlier of two elves
+// carrying the same counting as more, so ties always go the same way
+#[derive(Debug, Copy, Clone)]
+struct Ranked(Elf);
+
+impl Ranked {
+    fn key(&self) -> (i32, Reverse<usize>) {
+        (self.0.total, Reverse(self.0.index))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+// The elves kept in `top`, most first
+fn ranked(top: TopK<Ranked>) -> Vec<Elf> {
+    let top: Vec<Elf> = top
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Ranked(elf)| elf)
+        .collect();
+    for (rank, elf) in top.iter().enumerate() {
+        debug!(rank, elf = elf.index + 1, calories = elf.total, "Top elf");
+    }
+    top
+}
+
+// Sum of the calories carried by the k elves carrying the most.
+pub fn sum_top_k(elves: &[Elf], k: usize) -> i32 {
+    top_k(elves, k).iter().map(|elf| elf.total).sum()
+}
+
+/// The k elves carrying the most, most first. Elves carrying the same come in
+/// the order they're in the input, and the later ones miss out if there's no
+/// room for them all.
+pub fn top_k(elves: &[Elf], k: usize) -> Vec<Elf> {
+    let mut top = TopK::new(k);
+    top.extend(elves.iter().copied().map(Ranked));
+    ranked(top)
+}
+
+/// Every elf in the file at `path`, read in chunks on `threads` threads. The
+/// same as reading the file and parsing it, but quicker for huge files.
+pub fn read_elves(path: &Path, threads: usize) -> Result<Vec<Elf>, Box<dyn Error>> {
+    let chunks = chunked::read_chunks(path, threads, Vec::new, |elves, elf| elves.push(elf))?;
+    let mut elves = vec![];
+    for chunk in chunks {
+        elves.extend(chunk.found.into_iter().map(|elf| Elf {
+            index: chunk.first + elf.index,
+            ..elf
+        }));
+    }
+    Ok(elves)
+}
+
+/// The [`top_k`] elves in the file at `path`, read in chunks on `threads`
+/// threads, without keeping every elf.
+pub fn top_k_file(path: &Path, k: usize, threads: usize) -> Result<Vec<Elf>, Box<dyn Error>> {
+    let chunks = chunked::read_chunks(
+        path,
+        threads,
+        || TopK::new(k),
+        |top, elf| top.push(Ranked(elf)),
+    )?;
+    let mut top = TopK::new(k);
+    for chunk in chunks {
+        let mut moved = TopK::new(k);
+        moved.extend(
+            chunk
+                .found
+                .into_sorted_vec()
+                .into_iter()
+                .map(|Ranked(elf)| {
+                    Ranked(Elf {
+                        index: chunk.first + elf.index,
+                        ..elf
+                    })
+                }),
+        );
+        top.merge(moved);
+    }
+    Ok(ranked(top))
+}
+
+/// The elves carrying the most, from [`top_k`], and how much that is
+/// altogether.
+pub fn top_k_report(top: &[Elf]) -> String {
+    let mut report = String::new();
+    for (rank, elf) in top.iter().enumerate() {
+        writeln!(
+            report,
+            "#{}: elf {} had {} calories",
+            rank + 1,
+            elf.index + 1,
+            elf.total
+        )
+        .unwrap();
+    }
+    write!(
+        report,
+        "The top {} had {} calories",
+        top.len(),
+        top.iter().map(|elf| elf.total).sum::<i32>()
+    )
+    .unwrap();
+    report
+}
+
+// The middle total, or halfway between the middle two
+fn median(elves: &[Elf]) -> Option<f64> {
+    let mut totals: Vec<i32> = elves.iter().map(|elf| elf.total).collect();
+    totals.sort();
+    let mid = totals.len() / 2;
+    match totals.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(totals[mid] as f64),
+        _ => Some((totals[mid - 1] as f64 + totals[mid] as f64) / 2.0),
+    }
+}
+
+const HISTOGRAM_BUCKETS: i64 = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+// How many elves' totals fall in each of a few equal ranges, as bars
+fn histogram(elves: &[Elf]) -> String {
+    let (Some(min), Some(max)) = (
+        elves.iter().map(|elf| elf.total as i64).min(),
+        elves.iter().map(|elf| elf.total as i64).max(),
+    ) else {
+        return String::new();
+    };
+    // Round up, so the largest total lands in the last bucket
+    let size = ((max - min) / HISTOGRAM_BUCKETS + 1).max(1);
+    let mut counts = vec![0; HISTOGRAM_BUCKETS as usize];
+    for elf in elves {
+        counts[((elf.total as i64 - min) / size) as usize] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0).max(1);
+    let mut bars = String::new();
+    for (i, &count) in counts.iter().enumerate() {
+        let start = min + i as i64 * size;
+        // Anything at all gets a bar, so it isn't lost next to the big ones
+        let bar = (count * HISTOGRAM_WIDTH).div_ceil(most);
+        let line = format!(
+            "{:>7} - {:>7} {:>5} {}",
+            start,
+            start + size - 1,
+            count,
+            "#".repeat(bar)
+        );
+        writeln!(bars, "{}", line.trim_end()).unwrap();
+    }
+    bars
+}
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| String::from("-"), |value| value.to_string())
+}
+
+/// A table of what each elf is carrying, followed by the median total and a
+/// histogram of the totals.
+pub fn stats_report(elves: &[Elf]) -> String {
+    let mut report = format!(
+        "{:>5} {:>5} {:>7} {:>9} {:>7}\n",
+        "elf", "items", "total", "mean", "max"
+    );
+    for elf in elves {
+        writeln!(
+            report,
+            "{:>5} {:>5} {:>7} {:>9} {:>7}",
+            elf.index + 1,
+            elf.items,
+            elf.total,
+            or_dash(elf.mean().map(|mean| format!("{:.1}", mean))),
+            or_dash(elf.max_item)
+        )
+        .unwrap();
+    }
+    writeln!(report).unwrap();
+    writeln!(
+        report,
+        "Median total: {}",
+        or_dash(median(elves).map(|median| format!("{:.1}", median)))
+    )
+    .unwrap();
+    report += &histogram(elves);
+    report
+}
+
+// Each elf's lines, which are separated by a blank line. The last elf's
+// needn't be followed by one, but a blank line on its own still counts as an
+// elf carrying nothing.
+fn groups(input: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = input.lines().peekable();
+    std::iter::from_fn(move || {
+        lines.peek()?;
+        Some(lines.by_ref().take_while(|s| !s.is_empty()).collect())
+    })
+}
+
+// The elves in `input`
+fn elves(input: &str) -> impl Iterator<Item = Result<Elf, ParseError>> + '_ {
+    groups(input).enumerate().map(move |(index, group)| {
+        let mut elf = Elf::new(index);
+        for s in group {
+            elf.add(parse_in(input, s)?)
+                .ok_or_else(|| ParseError::new(input, s, "Too many calories for one elf"))?;
+        }
+        Ok(elf)
+    })
+}
+
+// Files bigger than this are read in chunks on every core
+const CHUNKED_MIN_BYTES: u64 = 16 << 20;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    // What each elf is carrying
+    type Input = Vec<Elf>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(elves(input).collect::<Result<_, _>>()?)
+    }
+
+    fn parse_file(&self, path: &Path) -> Result<Self::Input, Box<dyn Error>> {
+        if fs::metadata(path)?.len() < CHUNKED_MIN_BYTES {
+            return self.parse(&fs::read_to_string(path)?);
+        }
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        read_elves(path, threads)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<i32, Box<dyn Error>> {
+        Ok(sum_top_k(input, 1))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<i32, Box<dyn Error>> {
+        Ok(sum_top_k(input, 3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        path::PathBuf,
+        process,
+        sync::atomic::{self, AtomicUsize},
+    };
+
+    use aoc_common::solution::solve_all;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in ["1000\nabc\n\n", "1000\n-\n\n", "2147483647\n1\n\n"] {
+            assert!(solve_all(&Day1, input).is_err(), "{:?}", input);
+        }
+    }
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n";
+
+    #[test]
+    fn last_elf_neednt_end_with_a_blank_line() {
+        for input in [EXAMPLE, EXAMPLE.trim_end(), &EXAMPLE[..EXAMPLE.len() - 1]] {
+            let elves = Day1.parse(input).unwrap();
+            assert_eq!(elves.len(), 5, "{:?}", input);
+            assert_eq!(elves[4].total, 10000);
+        }
+        assert_eq!(Day1.parse("").unwrap(), []);
+        assert_eq!(Day1.parse("\n").unwrap(), [Elf::new(0)]);
+        assert_eq!(solve_all(&Day1, "1000\n\n2000").unwrap(), ["2000", "3000"]);
+    }
+
+    #[test]
+    fn reports_the_top_k() {
+        let elves = Day1.parse(EXAMPLE).unwrap();
+        assert_eq!(
+            top_k_report(&top_k(&elves, 2)),
+            "#1: elf 4 had 24000 calories\n#2: elf 3 had 11000 calories\nThe top 2 had 35000 calories"
+        );
+        assert_eq!(top_k_report(&[]), "The top 0 had 0 calories");
+    }
+
+    #[test]
+    fn ties_go_to_the_first_elves() {
+        let elves = Day1.parse("5\n\n9\n\n5\n\n4\n1\n\n9").unwrap();
+        let top: Vec<usize> = top_k(&elves, 4).iter().map(|elf| elf.index).collect();
+        assert_eq!(top, [1, 4, 0, 2]);
+    }
+
+    #[test]
+    fn reports_stats_for_each_elf() {
+        let elves = Day1.parse("1000\n3000\n\n\n2000\n\n").unwrap();
+        assert_eq!(
+            stats_report(&elves),
+            "  elf items   total      mean     max
+    1     2    4000    2000.0    3000
+    2     0       0         -       -
+    3     1    2000    2000.0    2000
+
+Median total: 2000.0
+      0 -     400     1 ########################################
+    401 -     801     0
+    802 -    1202     0
+   1203 -    1603     0
+   1604 -    2004     1 ########################################
+   2005 -    2405     0
+   2406 -    2806     0
+   2807 -    3207     0
+   3208 -    3608     0
+   3609 -    4009     1 ########################################
+"
+        );
+        assert_eq!(
+            stats_report(&[]),
+            "  elf items   total      mean     max\n\nMedian total: -\n"
+        );
+    }
+
+    static FILES: AtomicUsize = AtomicUsize::new(0);
+
+    // Write `input` to a file of its own, which is removed when dropped
+    struct TempInput(PathBuf);
+
+    impl TempInput {
+        fn new(input: &str) -> TempInput {
+            let n = FILES.fetch_add(1, atomic::Ordering::Relaxed);
+            let path = env::temp_dir().join(format!("day1-{}-{}.txt", process::id(), n));
+            fs::write(&path, input).unwrap();
+            TempInput(path)
+        }
+    }
+
+    impl Drop for TempInput {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn numbers_lines_in_errors_from_the_start_of_the_file() {
+        let input = "1\n\n".repeat(50) + "2\nx\n\n3\n";
+        let file = TempInput::new(&input);
+        let err = read_elves(&file.0, 4).unwrap_err();
+        assert_eq!(err.to_string(), Day1.parse(&input).unwrap_err().to_string());
+        assert!(
+            err.to_string().starts_with("line 102, column 1:"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn reads_big_files_in_chunks() {
+        let elf = "1000\n2000\n\n";
+        let input = elf.repeat(CHUNKED_MIN_BYTES as usize / elf.len() + 1);
+        let file = TempInput::new(&input);
+        assert!(fs::metadata(&file.0).unwrap().len() >= CHUNKED_MIN_BYTES);
+        let elves = Day1.parse_file(&file.0).unwrap();
+        assert_eq!(elves, Day1.parse(&input).unwrap());
+    }
+
+    // The obvious way to find the top K, to check the streaming one against
+    fn sum_top_k_by_sorting(calorie_counts: &[i32], k: usize) -> i32 {
+        let mut sorted = calorie_counts.to_vec();
+        sorted.sort_by(|a, b| b.cmp(a));
+        sorted.iter().take(k).sum()
+    }
+
+    proptest! {
+        #[test]
+        fn chunks_match_one_string(
+            input in "(([0-9x]{1,4})?\r?\n){0,60}[0-9]{0,4}",
+            k in 0..5usize,
+            threads in 1..8usize,
+        ) {
+            let file = TempInput::new(&input);
+            match Day1.parse(&input) {
+                Ok(elves) => {
+                    prop_assert_eq!(read_elves(&file.0, threads).unwrap(), elves.clone());
+                    prop_assert_eq!(top_k_file(&file.0, k, threads).unwrap(), top_k(&elves, k));
+                }
+                Err(err) => {
+                    // Errors are the same apart from where in the input they
+                    // think they are, which isn't shown
+                    let err = err.to_string();
+                    prop_assert_eq!(read_elves(&file.0, threads).unwrap_err().to_string(), err.clone());
+                    prop_assert_eq!(top_k_file(&file.0, k, threads).unwrap_err().to_string(), err);
+                }
+            }
+        }
+
+        #[test]
+        fn top_k_matches_sorting(calorie_counts in prop::collection::vec(0..100_000i32, 0..50)) {
+            let elves: Vec<Elf> = calorie_counts
+                .iter()
+                .enumerate()
+                .map(|(index, &total)| Elf { index, items: 1, total, max_item: Some(total) })
+                .collect();
+            prop_assert_eq!(
+                sum_top_k(&elves, 1),
+                sum_top_k_by_sorting(&calorie_counts, 1)
+            );
+            prop_assert_eq!(
+                sum_top_k(&elves, 3),
+                sum_top_k_by_sorting(&calorie_counts, 3)
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::error::Error;
+
+use aoc_common::{
+    parser::{self, int, map, or, preceded, tag, Parser},
+    visual::{self, Colour, Frame},
+    Solution,
+};
+use tracing::trace;
+
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+fn instruction<'a>() -> impl Parser<'a, Instruction> {
+    or(
+        map(tag("noop"), |_| Instruction::Noop),
+        map(preceded(tag("addx "), int()), Instruction::Addx),
+    )
+}
+
+struct XReg {
+    reg: i32,
+    pipeline: Vec<Option<i32>>,
+    pos: usize,
+}
+
+impl XReg {
+    fn new(init_val: i32, pipeline_length: usize) -> Self {
+        Self {
+            reg: init_val,
+            pipeline: vec![None; pipeline_length],
+            pos: 0,
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Some(val) = self.pipeline[self.pos] {
+            // Add the value. Silly programs could overflow the register, so
+            // just pin it at the limit if they do.
+            self.reg = self.reg.saturating_add(val);
+            self.pipeline[self.pos] = None;
+        }
+
+        // Increment pos
+        self.pos = (self.pos + 1) % self.pipeline.len();
+    }
+
+    fn issue_add(&mut self, val: i32) {
+        let plen = self.pipeline.len();
+        let last_idx = (self.pos + (plen - 1)) % plen;
+        self.pipeline[last_idx] = Some(val);
+    }
+
+    fn get_curr_val(&self) -> i32 {
+        self.reg
+    }
+}
+
+const CRT_DIMS: [usize; 2] = [6, 40];
+
+// A picture of the CRT as drawn so far, with the beam in red and the
+// sprite's position underneath it
+fn draw(cycle: usize, crt: &[char], sprite_x: i32) -> Frame {
+    let mut rows: Vec<Vec<(char, Colour)>> = crt
+        .chunks(CRT_DIMS[1])
+        .map(|row| {
+            row.iter()
+                .map(|&c| {
+                    (
+                        c,
+                        if c == '#' {
+                            Colour::Green
+                        } else {
+                            Colour::Grey
+                        },
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    if let Some(beam) = rows.last_mut().and_then(|row| row.last_mut()) {
+        beam.1 = Colour::Red;
+    }
+    rows.push(vec![]);
+    rows.push(
+        (0..CRT_DIMS[1] as i32)
+            .map(|x| match x.abs_diff(sprite_x) <= 1 {
+                true => ('#', Colour::Blue),
+                false => (' ', Colour::Default),
+            })
+            .collect(),
+    );
+    Frame {
+        caption: format!("Cycle {}, X = {}", cycle, sprite_x),
+        rows,
+    }
+}
+
+// Run the program, returning the sum of the signal strengths sampled for
+// part 1 and the pixels drawn on the CRT for part 2.
+fn run(program: &[Instruction]) -> (i32, Vec<char>) {
+    // There is a 2 cycle delay before values are updated.
+    // Values are updated 'between' cycles though, so we
+    // insert an extra delay
+    const PIPELINE_LEN: usize = 2;
+
+    let mut xreg = XReg::new(1, PIPELINE_LEN);
+    let mut cycle: usize = 1;
+
+    let mut res: i32 = 0;
+
+    let mut capture_result_pt1 = |val, cycle| {
+        res = res.saturating_add((cycle as i32).saturating_mul(val));
+    };
+
+    let num_pixels: usize = CRT_DIMS.into_iter().product();
+
+    let mut crt_storage: Vec<char> = Vec::with_capacity(num_pixels);
+
+    for instr in program {
+        let cycles = match instr {
+            Instruction::Noop => 1,
+            Instruction::Addx(val) => {
+                xreg.issue_add(*val);
+                2
+            }
+        };
+
+        for _ in 0..cycles {
+            // Current cycle logic goes here
+            let curr_val = xreg.get_curr_val();
+
+            if (cycle + 20).is_multiple_of(40) {
+                capture_result_pt1(curr_val, cycle);
+            }
+
+            // Cycle is 1-based indexing, crt_storage is 0-based
+            let cursor_x: i32 = ((cycle - 1) % CRT_DIMS[1]).try_into().unwrap();
+            let sprite_x: i32 = curr_val;
+
+            trace!(cycle, cursor_x, sprite_x, "Drawing");
+
+            // See if the sprite position overlaps the cursor position
+            if cursor_x.abs_diff(sprite_x) <= 1 {
+                crt_storage.push('#');
+            } else {
+                crt_storage.push('.');
+            }
+            visual::frame(|| draw(cycle, &crt_storage, sprite_x));
+
+            xreg.tick();
+            cycle += 1;
+        }
+    }
+
+    (res, crt_storage)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    // The answer is the letters displayed on the CRT, so we give back the
+    // image for a human to read.
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parser::lines(input, &instruction())?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<i32, Box<dyn Error>> {
+        Ok(run(input).0)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let crt_storage = run(input).1;
+        let mut image = String::with_capacity(crt_storage.len() + CRT_DIMS[0]);
+        for (index, c) in crt_storage.into_iter().enumerate() {
+            if index != 0 && index % CRT_DIMS[1] == 0 {
+                image.push('\n');
+            }
+            image.push(c);
+        }
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "noop\naddx",
+            "noop\nmulx 3\n",
+            "addx three\n",
+            "noop\n\nnoop\n",
+            "addx 99999999999\n",
+        ] {
+            assert!(solve_all(&Day10, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn huge_values_dont_overflow() {
+        let input = "addx 2147483647\n".repeat(30);
+        assert!(solve_all(&Day10, &input).is_ok());
+    }
+}
@@ -0,0 +1,378 @@
+use std::{error::Error, num::NonZeroU32};
+
+use aoc_common::{
+    parser::{
+        self, int, key_value, line_end, map, one_of, or, pair, preceded, separated, tag,
+        terminated, try_map, uint, PResult, Parser,
+    },
+    NoSolutionError, ParseError, Solution, TopK,
+};
+use tracing::debug;
+
+// Represents an operand in an operation
+#[derive(Copy, Clone, Debug)]
+enum Operand {
+    Literal(i64),
+    Old, // symbolic
+}
+
+fn operand<'a>() -> impl Parser<'a, Operand> {
+    or(
+        map(tag("old"), |_| Operand::Old),
+        map(uint(), Operand::Literal),
+    )
+}
+
+#[derive(Copy, Clone, Debug)]
+enum OpType {
+    Add,
+    Mul,
+}
+
+#[derive(Clone, Debug)]
+struct Operation {
+    lhs: Operand,
+    rhs: Operand,
+    optype: OpType,
+}
+
+type MonkeyID = usize;
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<i64>,
+    op: Operation,
+    test: i64,
+    outs: [MonkeyID; 2],
+}
+
+// Parse the starting state of a monkey, along with its number, from its
+// block of lines in the input.
+pub fn parse_monkey(s: &str) -> PResult<'_, (MonkeyID, Monkey)> {
+    let header = terminated(preceded(tag("Monkey "), uint()), pair(tag(":"), line_end()));
+    let (id, s) = header.parse(s)?;
+
+    // A monkey might not start with any items
+    let items = key_value("Starting items", separated(int(), tag(", ")));
+    let (items, s) = items.parse(s)?;
+
+    let optype = one_of(&[("+", OpType::Add), ("*", OpType::Mul)]);
+    let operation = map(
+        preceded(
+            tag("new = "),
+            pair(
+                operand(),
+                pair(preceded(tag(" "), optype), preceded(tag(" "), operand())),
+            ),
+        ),
+        |(lhs, (optype, rhs))| Operation { lhs, rhs, optype },
+    );
+    let (op, s) = key_value("Operation", operation).parse(s)?;
+
+    let divisor = try_map(uint(), |test: i64| match test {
+        0 => Err(String::from("Can't test for divisibility by 0")),
+        test => Ok(test),
+    });
+    let (test, s) = key_value("Test", preceded(tag("divisible by "), divisor)).parse(s)?;
+
+    let throw = || preceded(tag("throw to monkey "), uint::<MonkeyID>());
+    let (monkey1, s) = key_value("If true", throw()).parse(s)?;
+    let (monkey0, s) = key_value("If false", throw()).parse(s)?;
+
+    Ok((
+        (
+            id,
+            Monkey {
+                items,
+                op,
+                test,
+                outs: [monkey0, monkey1],
+            },
+        ),
+        s,
+    ))
+}
+
+fn get_monkey_triplet(v: &mut [Monkey], idx: MonkeyID) -> (&mut Monkey, [&mut Monkey; 2]) {
+    unsafe {
+        let m = &mut v[idx];
+        let idx0 = m.outs[0];
+        let idx1 = m.outs[1];
+        assert!(idx != idx0 && idx != idx1 && idx0 != idx1);
+        let m = m as *mut Monkey;
+
+        let out0 = &mut v[idx0] as *mut Monkey;
+        let out1 = &mut v[idx1] as *mut Monkey;
+        (&mut *m, [&mut *out0, &mut *out1])
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+// None if the lcm doesn't fit in an i64
+fn lcm(nums: &[i64]) -> Option<i64> {
+    let a = nums[0];
+    if nums.len() == 1 {
+        return Some(a);
+    }
+    let b = lcm(&nums[1..])?;
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn worry_overflowed() -> NoSolutionError {
+    NoSolutionError(String::from("Worry level is too big to keep track of"))
+}
+
+// How a game of keep away is played. The two parts of the puzzle only differ
+// in how long the monkeys play for and whether our worry drops after each
+// inspection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub rounds: usize,
+    // Our worry level is divided by this after each inspection, 1 for no
+    // relief. It can't be 0, or we'd divide by it.
+    pub relief: NonZeroU32,
+}
+
+impl Rules {
+    pub const PART1: Rules = Rules {
+        rounds: 20,
+        relief: NonZeroU32::new(3).unwrap(),
+    };
+    pub const PART2: Rules = Rules {
+        rounds: 10000,
+        relief: NonZeroU32::MIN,
+    };
+
+    /// Rules for `rounds` rounds with our worry divided by `relief`, or None
+    /// if `relief` is 0.
+    pub fn new(rounds: usize, relief: u32) -> Option<Rules> {
+        Some(Rules {
+            rounds,
+            relief: NonZeroU32::new(relief)?,
+        })
+    }
+}
+
+fn round(
+    v: &mut [Monkey],
+    c: &mut [usize],
+    wrap_to: i64,
+    relief: NonZeroU32,
+) -> Result<(), NoSolutionError> {
+    for (idx, count) in c.iter_mut().enumerate() {
+        let (from, outs) = get_monkey_triplet(v, idx);
+
+        // Apply the update to each item in order
+        for item in &mut from.items {
+            *item %= wrap_to;
+
+            let lhs = match from.op.lhs {
+                Operand::Literal(val) => val,
+                Operand::Old => *item,
+            };
+            let rhs = match from.op.rhs {
+                Operand::Literal(val) => val,
+                Operand::Old => *item,
+            };
+            *item = match from.op.optype {
+                OpType::Add => lhs.checked_add(rhs),
+                OpType::Mul => lhs.checked_mul(rhs),
+            }
+            .ok_or_else(worry_overflowed)?;
+
+            *item /= i64::from(relief.get());
+
+            // Update count for this monkey
+            *count += 1;
+        }
+        for item in &from.items {
+            let test = (item % from.test == 0) as usize;
+            outs[test].items.push(*item);
+        }
+        from.items.clear();
+    }
+    Ok(())
+}
+
+// Run the monkeys for the number of rounds the rules ask for, and work out
+// the level of monkey business from the two most active monkeys.
+pub fn monkey_business(monkeys: &[Monkey], rules: &Rules) -> Result<usize, NoSolutionError> {
+    let mut monkeys = monkeys.to_vec();
+    let wrap_to =
+        lcm(&monkeys.iter().map(|m| m.test).collect::<Vec<i64>>()).ok_or_else(worry_overflowed)?;
+
+    let mut inspection_counts: Vec<usize> = vec![0usize; monkeys.len()];
+    for _ in 0..rules.rounds {
+        round(&mut monkeys, &mut inspection_counts, wrap_to, rules.relief)?;
+    }
+
+    debug!(
+        rounds = rules.rounds,
+        ?inspection_counts,
+        "Finished the rounds"
+    );
+
+    // Get top 2 monkeys in terms of activity
+    let mut top2 = TopK::new(2);
+    top2.extend(inspection_counts);
+    let top2 = top2.into_sorted_vec();
+    debug!(?top2, "Top 2 inspection counts");
+    if top2.len() < 2 {
+        return Err(NoSolutionError(String::from(
+            "There need to be at least two monkeys",
+        )));
+    }
+    Ok(top2.iter().product::<usize>())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let mut monkeys = vec![];
+        for block in parser::blocks(input) {
+            let (monkey_idx, monkey) = parser::parse_all(input, block, &parse_monkey)?;
+            if monkey_idx != monkeys.len() {
+                let header = block.lines().next().unwrap_or(block);
+                return Err(Box::new(ParseError::new(
+                    input,
+                    header,
+                    format!("Expected monkey {} next", monkeys.len()),
+                )));
+            }
+            monkeys.push(monkey);
+        }
+
+        // Monkeys can only throw to other monkeys that exist, and we need
+        // two of them to be different from the thrower and each other.
+        if monkeys.is_empty() {
+            return Err(Box::new(NoSolutionError(String::from(
+                "There are no monkeys",
+            ))));
+        }
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            let [out0, out1] = monkey.outs;
+            if out0 >= monkeys.len() || out1 >= monkeys.len() {
+                return Err(Box::new(NoSolutionError(format!(
+                    "Monkey {} throws to a monkey that doesn't exist",
+                    idx
+                ))));
+            }
+            if out0 == idx || out1 == idx || out0 == out1 {
+                return Err(Box::new(NoSolutionError(format!(
+                    "Monkey {} must throw to two other monkeys",
+                    idx
+                ))));
+            }
+        }
+        Ok(monkeys)
+    }
+
+    // Part 1 runs for 20 rounds, and our worry drops after each inspection
+    fn part1(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(monkey_business(input, &Rules::PART1)?)
+    }
+
+    // Part 2 runs for 10000 rounds without any relief
+    fn part2(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(monkey_business(input, &Rules::PART2)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    const MONKEYS: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items:
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 0
+";
+
+    #[test]
+    fn parses_example() {
+        assert!(solve_all(&Day11, MONKEYS).is_ok());
+    }
+
+    #[test]
+    fn broken_input_is_an_error() {
+        let breakages = [
+            ("Monkey 2:", "Monkey 3:"),
+            ("Monkey 2:", "Gorilla 2:"),
+            ("79, 98", "79, x"),
+            ("old * 19", "old / 19"),
+            ("old * 19", "old * 99999999999999999999"),
+            ("divisible by 23", "divisible by 0"),
+            ("divisible by 23", "divisible by"),
+            (
+                "throw to monkey 2\n    If false: throw to monkey 1",
+                "throw to monkey 1\n    If false: throw to monkey 1",
+            ),
+            ("If false: throw to monkey 1", "If false: throw to monkey 0"),
+            ("If false: throw to monkey 1", "If false: throw to monkey 7"),
+            ("old * 19", "old * 9999999999999999"),
+        ];
+        for (from, to) in breakages {
+            let input = MONKEYS.replacen(from, to, 1);
+            assert!(solve_all(&Day11, &input).is_err(), "{:?}", input);
+        }
+
+        // Stopping part way through a monkey
+        for len in [10, 40, 80, 110, 130] {
+            let input = &MONKEYS[..len];
+            assert!(solve_all(&Day11, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn relief_cant_be_zero() {
+        assert_eq!(Rules::new(20, 0), None);
+        assert_eq!(Rules::new(20, 3), Some(Rules::PART1));
+        let monkeys = Day11.parse(MONKEYS).unwrap();
+        let rules = Rules::new(20, 3).unwrap();
+        let business = monkey_business(&monkeys, &rules).unwrap();
+        assert_eq!(business.to_string(), solve_all(&Day11, MONKEYS).unwrap()[0]);
+    }
+
+    // Monkeys cut off part way through, or with silly numbers. Add anything
+    // that fuzz/ finds here too, it just mustn't panic.
+    #[test]
+    fn fuzz_regressions() {
+        for input in [
+            "Monkey 0:",
+            "Monkey 0:\n  Starting items: 1,",
+            "Monkey 99999999999999999999999:",
+            "Monkey 0:\n  Starting items: 1\n  Operation: new = old",
+        ] {
+            let _ = parse_monkey(input);
+        }
+    }
+}
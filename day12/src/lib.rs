@@ -1,228 +1,277 @@
-use std::collections::HashMap;
-
-use std::fmt;
-use std::hash::Hash;
-
-// Sorted based on key, ordering of pairs with matching keys is undefined.
-
-pub struct MinHeapKeyValue<K, V>
-where
-    K: PartialOrd,
-    V: PartialOrd,
-{
-    // Stores the actual values
-    heap: Vec<(K, V)>,
-    // Stores a reverse mapping from the value (which
-    // must be unique) to the index in the heap.
-    reverse_mapping: HashMap<V, usize>,
-}
+use std::{collections::HashSet, error::Error};
 
-impl<K, V> MinHeapKeyValue<K, V>
-where
-    K: fmt::Debug + Copy + PartialOrd,
-    V: fmt::Debug + Copy + PartialOrd + Hash + Eq,
-{
-    fn left_child(idx: usize) -> usize {
-        idx * 2 + 1
-    }
-    fn right_child(idx: usize) -> usize {
-        idx * 2 + 2
-    }
-    fn parent(idx: usize) -> usize {
-        (idx - 1) / 2
-    }
+use aoc_common::{Grid, NoSolutionError, ParseError, Solution, Vec2};
 
-    /// Performs a bubble up on the element at the given index,
-    /// and returns the index that element ends up at.
-    /// Assumes that
-    fn bubble_up(&mut self, idx: usize) {
-        let mut curr = idx;
-        while curr > 0 {
-            let p = Self::parent(curr);
-            if self.heap[curr] >= self.heap[p] {
-                break;
-            }
-            self.reverse_mapping.insert(self.heap[p].1, curr);
-            self.heap.swap(curr, p);
-            curr = p;
-        }
-        self.reverse_mapping.insert(self.heap[curr].1, curr);
-    }
+mod heap;
 
-    fn swap(&mut self, a: usize, b: usize) {
-        // Swap elements in the heap, but also update our
-        // reverse mapping.
-        let aVal = self.heap[a].1;
-        let bVal = self.heap[b].1;
-        self.heap.swap(a, b);
-        self.reverse_mapping.insert(aVal, b);
-        self.reverse_mapping.insert(bVal, a);
-    }
+pub use heap::MinHeapKeyValue;
 
-    /// Ensure the tree starting at root idx meets our invariants.
-    /// It is assumed that the children of idx form trees that
-    /// meet the invariants however.
-    fn min_heapify(&mut self, idx: usize) {
-        let l = Self::left_child(idx);
-        let r = Self::right_child(idx);
-        let mut s = idx;
-        if l < self.heap.len() && self.heap[l] < self.heap[s] {
-            s = l;
-        }
-        if r < self.heap.len() && self.heap[r] < self.heap[s] {
-            s = r;
-        }
-        // If the smallest value is not the parent but one of the children,
-        // swap parent and child and recurse.
-        // and re
-        if s != idx {
-            self.swap(idx, s);
-            self.min_heapify(s);
+fn pt1(terrain: &Grid<u8>, start: Vec2, end: Vec2) -> u32 {
+    // Run djikstra's on the graph formed by coordinates in the terrain.
+    let mut next = MinHeapKeyValue::new();
+
+    let mut visited = Grid::new(terrain.dims(), false);
+    let mut distances = Grid::new(terrain.dims(), u32::MAX);
+
+    visited[start] = true;
+    distances[start] = 0;
+    next.insert(0u32, start);
+
+    while let Some((distance, coord)) = next.pop() {
+        if coord == end {
+            // We have found the shortest path, break
+            break;
         }
-    }
+        visited[coord] = true;
 
-    pub fn new() -> Self {
-        Self {
-            heap: Vec::new(),
-            reverse_mapping: HashMap::new(),
+        for neighbour in terrain.neighbours4(coord) {
+            // If the height differential is greater than +1, we can't go in that
+            // direction
+            if terrain[coord] + 1 < terrain[neighbour] {
+                continue;
+            }
+            if visited[neighbour] {
+                continue;
+            }
+            // Otherwise update neighbour's distance, and add it to the set to visit
+            let new_distance = distance + 1;
+            let neighbour_distance = &mut distances[neighbour];
+            if new_distance < *neighbour_distance {
+                *neighbour_distance = new_distance;
+                next.insert_or_decrease_key(neighbour, new_distance);
+            }
         }
     }
+    distances[end]
+}
 
-    pub fn insert(&mut self, key: K, value: V) {
-        // Insert at back of heap.
-        self.heap.push((key, value));
+fn pt2(terrain: &Grid<u8>, starts: &[Vec2], end: Vec2) -> u32 {
+    // Make the start points into a set
+    let mut end_coords: HashSet<Vec2> = starts.iter().copied().collect();
 
-        // Bubble up will also insert the reverse mapping we need
-        self.bubble_up(self.heap.len() - 1);
-    }
+    // We'll actually start from the end point, and walk towards the start points.
+    let mut next = MinHeapKeyValue::new();
 
-    /// Take the minimum element off the heap and return it.
-    /// Returns None if the heap is empty.
-    pub fn pop(&mut self) -> Option<(K, V)> {
-        if self.heap.is_empty() {
-            return None;
-        }
-        if self.heap.len() == 1 {
-            self.reverse_mapping.clear();
-            return self.heap.pop();
+    let mut visited = Grid::new(terrain.dims(), false);
+    let mut distances = Grid::new(terrain.dims(), u32::MAX);
+
+    visited[end] = true;
+    distances[end] = 0;
+    next.insert(0u32, end);
+
+    let mut min_distance = u32::MAX;
+    while let Some((distance, coord)) = next.pop() {
+        if end_coords.remove(&coord) {
+            // Update the min distance
+            if distances[coord] < min_distance {
+                min_distance = distances[coord];
+            }
+            // Finish if the set of end coordinates is empty
+            if end_coords.is_empty() {
+                break;
+            }
         }
+        visited[coord] = true;
 
-        // Swap first element to the back
-        let last_idx = self.heap.len() - 1;
-        self.heap.swap(0, last_idx);
+        for neighbour in terrain.neighbours4(coord) {
+            if terrain[neighbour] + 1 < terrain[coord] {
+                continue;
+            }
 
-        // Take the result
-        let res = self.heap.pop().unwrap();
-        let resVal = &res.1;
+            if visited[neighbour] {
+                continue;
+            }
 
-        let otherVal = self.heap[0].1;
+            // Otherwise update neighbour's distance, and add it to the set to visit
+            let new_distance = distance + 1;
+            let neighbour_distance = &mut distances[neighbour];
+            if new_distance < *neighbour_distance {
+                *neighbour_distance = new_distance;
+                next.insert_or_decrease_key(neighbour, new_distance);
+            }
+        }
+    }
 
-        // Update the reverse lookup
-        self.reverse_mapping.remove(resVal);
-        self.reverse_mapping.insert(otherVal, 0);
+    min_distance
+}
 
-        self.min_heapify(0);
+fn unreachable_end() -> NoSolutionError {
+    NoSolutionError(String::from("There's no way to climb to the end"))
+}
 
-        Some(res)
-    }
+pub struct Terrain {
+    // Height of each position, 0 for 'a' up to 25 for 'z'
+    heights: Grid<u8>,
+    start: Vec2,
+    end: Vec2,
+}
 
-    /// Special case to update one of the keys for heap elements to a smaller
-    /// value. Panics if the key is not smaller than the existing one.
-    /// Does nothing if the key doesn't exist
-    pub fn decrease_key(&mut self, value: &V, new_key: K) -> bool {
-        if let Some(idx) = self.reverse_mapping.get(value) {
-            let curr_key = &mut self.heap[*idx].0;
-            assert!(*curr_key >= new_key);
-
-            *curr_key = new_key;
-
-            // Now bubble up from this idx
-            self.bubble_up(*idx);
-            true
-        } else {
-            // Otherwise do nothing
-            false
-        }
+// Basically, parse the input into an array of integer heights, perform A* pathfinding where
+// each position is a node in a graph, and a node is connected to another node if it is
+// neighbouring and the height change is no more than 1.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Terrain;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let (mut start, mut end) = (None, None);
+
+        let heights = Grid::parse(input, |coord, c| {
+            let (pos, name, height) = match c {
+                'S' => (&mut start, "start", 'a'),
+                'E' => (&mut end, "end", 'z'),
+                'a'..='z' => return Ok(c as u8 - b'a'),
+                _ => {
+                    return Err(String::from(
+                        "Expected a height from 'a' to 'z', or 'S' or 'E'",
+                    ))
+                }
+            };
+            if pos.replace(coord).is_some() {
+                return Err(format!("There can only be one {} position", name));
+            }
+            Ok(height as u8 - b'a')
+        })?;
+
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                return Err(Box::new(ParseError::new(
+                    input,
+                    &input[input.len()..],
+                    "Expected the map to have a start 'S' and an end 'E'",
+                )))
+            }
+        };
+
+        Ok(Terrain {
+            heights,
+            start,
+            end,
+        })
     }
 
-    pub fn insert_or_decrease_key(&mut self, value: V, key: K) {
-        if !self.decrease_key(&value, key) {
-            self.insert(key, value);
+    fn part1(&self, terrain: &Self::Input) -> Result<u32, Box<dyn Error>> {
+        let distance = pt1(&terrain.heights, terrain.start, terrain.end);
+        if distance == u32::MAX {
+            return Err(Box::new(unreachable_end()));
         }
+        Ok(distance)
     }
 
-    /// Get a reference to the key associated with the given value
-    pub fn get_key(&self, value: &V) -> Option<&K> {
-        let idx = self.reverse_mapping.get(value)?;
-        let pair = self.heap.get(*idx)?;
-        Some(&pair.0)
+    fn part2(&self, terrain: &Self::Input) -> Result<u32, Box<dyn Error>> {
+        let starts: Vec<Vec2> = terrain
+            .heights
+            .iter()
+            .filter(|(_, &height)| height == 0)
+            .map(|(coord, _)| coord)
+            .collect();
+
+        let distance = pt2(&terrain.heights, &starts, terrain.end);
+        if distance == u32::MAX {
+            return Err(Box::new(unreachable_end()));
+        }
+        Ok(distance)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MinHeapKeyValue;
-    #[test]
-    fn insert_pop_one() {
-        let mut heap = MinHeapKeyValue::<i32, usize>::new();
-        heap.insert(10, 0);
+    use std::collections::VecDeque;
 
-        assert_eq!(heap.pop(), Some((10, 0)));
-        assert_eq!(heap.pop(), None);
-        assert_eq!(heap.pop(), None);
-    }
+    use aoc_common::solution::solve_all;
+    use proptest::prelude::*;
+
+    use super::*;
 
     #[test]
-    fn insert_reverse_order() {
-        let mut heap = MinHeapKeyValue::<i32, usize>::new();
-
-        heap.insert(10, 0);
-        heap.insert(9, 1);
-        heap.insert(8, 2);
-        heap.insert(7, 3);
-
-        // Expect the values to come out in reverse order
-        assert_eq!(heap.pop(), Some((7, 3)));
-        assert_eq!(heap.pop(), Some((8, 2)));
-        assert_eq!(heap.pop(), Some((9, 1)));
-        assert_eq!(heap.pop(), Some((10, 0)));
-        assert_eq!(heap.pop(), None);
+    fn broken_input_is_an_error() {
+        for input in [
+            "",
+            "Sbc\nabE\nab\n",
+            "Sbc\nab#\nabE\n",
+            "Sbc\nabc\nabc\n",
+            "SbE\nSbc\n",
+            "SbE\nabE\n",
+            "Szz\nzzE\n",
+            "Sé\nbE\n",
+        ] {
+            assert!(solve_all(&Day12, input).is_err(), "{:?}", input);
+        }
     }
 
-    #[test]
-    fn get_key_after_insert() {
-        let mut heap = MinHeapKeyValue::<i32, usize>::new();
-
-        heap.insert(10, 0);
-        assert_eq!(heap.get_key(&0), Some(&10));
-        assert_eq!(heap.get_key(&1), None);
-        assert_eq!(heap.get_key(&2), None);
-
-        heap.insert(9, 1);
-        assert_eq!(heap.get_key(&0), Some(&10));
-        assert_eq!(heap.get_key(&1), Some(&9));
-        assert_eq!(heap.get_key(&2), None);
+    // Every step costs the same, so a breadth first search from all of the
+    // starts at once finds the shortest path to the end too.
+    fn bfs(terrain: &Grid<u8>, starts: &[Vec2], end: Vec2) -> Option<u32> {
+        let mut distances = Grid::new(terrain.dims(), None);
+        let mut queue = VecDeque::new();
+        for &start in starts {
+            distances[start] = Some(0);
+            queue.push_back(start);
+        }
+        while let Some(coord) = queue.pop_front() {
+            let distance = distances[coord]?;
+            if coord == end {
+                return Some(distance);
+            }
+            for neighbour in terrain.neighbours4(coord) {
+                if terrain[neighbour] <= terrain[coord] + 1 && distances[neighbour].is_none() {
+                    distances[neighbour] = Some(distance + 1);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        None
     }
 
-    #[test]
-    fn decrease_key() {
-        let mut heap = MinHeapKeyValue::<i32, usize>::new();
-
-        heap.insert(10, 0);
-        heap.insert(9, 1);
-        heap.insert(8, 2);
-        heap.insert(7, 3);
-
-        // Decrease the key for value 0 and check we still get the right value with get_key and pop
-        heap.decrease_key(&0, 6);
-        assert_eq!(heap.get_key(&0), Some(&6));
-        assert_eq!(heap.get_key(&1), Some(&9));
-        assert_eq!(heap.get_key(&2), Some(&8));
-        assert_eq!(heap.get_key(&3), Some(&7));
-
-        assert_eq!(heap.pop(), Some((6, 0)));
-        assert_eq!(heap.pop(), Some((7, 3)));
-        assert_eq!(heap.pop(), Some((8, 2)));
-        assert_eq!(heap.pop(), Some((9, 1)));
+    // A small map with a start and end somewhere on it. Heights are kept
+    // close together so there's usually a way to the end.
+    fn map() -> impl Strategy<Value = String> {
+        (1..8usize, 1..8usize)
+            .prop_flat_map(|(width, height)| {
+                let cells = width * height;
+                (
+                    Just(width),
+                    prop::collection::vec(b'a'..=b'f', cells),
+                    0..cells,
+                    0..cells,
+                )
+            })
+            .prop_filter("start and end are different", |(_, _, start, end)| {
+                start != end
+            })
+            .prop_map(|(width, mut cells, start, end)| {
+                cells[start] = b'S';
+                cells[end] = b'E';
+                cells
+                    .chunks(width)
+                    .map(|row| String::from_utf8(row.to_vec()).unwrap() + "\n")
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn dijkstra_matches_bfs(input in map()) {
+            let terrain = Solution::parse(&Day12, &input).unwrap();
+            let lowest: Vec<Vec2> = terrain
+                .heights
+                .iter()
+                .filter(|(_, &height)| height == 0)
+                .map(|(coord, _)| coord)
+                .collect();
+
+            prop_assert_eq!(
+                Day12.part1(&terrain).ok(),
+                bfs(&terrain.heights, &[terrain.start], terrain.end)
+            );
+            prop_assert_eq!(
+                Day12.part2(&terrain).ok(),
+                bfs(&terrain.heights, &lowest, terrain.end)
+            );
+        }
     }
 }
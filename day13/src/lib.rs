@@ -0,0 +1,230 @@
+use std::{cmp::Ordering, error::Error};
+
+use aoc_common::{NoSolutionError, ParseError, Solution};
+
+#[derive(Clone, Debug, Eq)]
+pub enum Entry {
+    List(Vec<Entry>),
+    Number(i32),
+}
+
+fn compare_lists(lhs: &[Entry], rhs: &[Entry]) -> Ordering {
+    let mut lhs_it = lhs.iter();
+    let mut rhs_it = rhs.iter();
+
+    loop {
+        let (lhs, rhs) = (lhs_it.next(), rhs_it.next());
+
+        // The following will return the ordering immediately once it is
+        // no longer ambiguous (i.e. not equal).
+        match (lhs, rhs) {
+            (None, None) => break,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => {
+                return Ordering::Greater;
+            }
+            (Some(lhs), Some(rhs)) => {
+                let res = lhs.cmp(rhs);
+                if res.is_ne() {
+                    return res;
+                }
+            }
+        }
+    }
+
+    Ordering::Equal
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Entry::Number(a), Entry::Number(b)) => a.cmp(b),
+            (Entry::List(a), Entry::List(b)) => compare_lists(a, b),
+            (Entry::Number(a), Entry::List(b)) => compare_lists(&[Entry::Number(*a)], b),
+            (Entry::List(a), Entry::Number(b)) => compare_lists(a, &[Entry::Number(*b)]),
+        }
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Move a number on the top of the stack into the list below it, now that
+// we've seen the end of it. Numbers are only ever on the top of the stack,
+// with a list below.
+fn finish_number(entry_stack: &mut Vec<Entry>) {
+    if let Some(Entry::Number(_)) = entry_stack.last() {
+        let e = entry_stack.pop().unwrap();
+        if let Some(Entry::List(list)) = entry_stack.last_mut() {
+            list.push(e);
+        }
+    }
+}
+
+// Parse one packet. Public so that it can be fuzzed, see fuzz/.
+pub fn parse_entry(line: &str) -> Result<Entry, ParseError> {
+    // The bottom of the stack collects everything on the line, and is never
+    // popped until the end.
+    let mut entry_stack = vec![Entry::List(vec![])];
+    let bad = |idx: usize, c: char, message: &str| {
+        ParseError::new(line, &line[idx..idx + c.len_utf8()], message)
+    };
+
+    for (idx, c) in line.char_indices() {
+        match c {
+            '[' => {
+                if let Some(Entry::Number(_)) = entry_stack.last() {
+                    return Err(bad(idx, c, "Expected ',' before '['"));
+                }
+                entry_stack.push(Entry::List(vec![]));
+            }
+            ']' => {
+                // Pop the top of the stack. It might be
+                // a number or it might be a list in the
+                // case that there were no entries in the
+                // array.
+                finish_number(&mut entry_stack);
+                if entry_stack.len() < 2 {
+                    return Err(bad(idx, c, "Unmatched ']'"));
+                }
+                let e = entry_stack.pop().unwrap();
+                if let Some(Entry::List(parent_list)) = entry_stack.last_mut() {
+                    parent_list.push(e);
+                }
+            }
+            '0'..='9' => {
+                // Start of or extension of a number
+                let value = c as i32 - '0' as i32;
+                if let Some(Entry::Number(n)) = entry_stack.last_mut() {
+                    *n = n
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(value))
+                        .ok_or_else(|| bad(idx, c, "Number is too big"))?;
+                } else {
+                    entry_stack.push(Entry::Number(value));
+                }
+            }
+            ',' => {
+                // End of a number, or separates lists
+                finish_number(&mut entry_stack);
+            }
+            _ => {
+                return Err(bad(
+                    idx,
+                    c,
+                    &format!("Unrecognised input character '{}'", c),
+                ));
+            }
+        }
+    }
+    finish_number(&mut entry_stack);
+    if entry_stack.len() > 1 {
+        return Err(ParseError::new(line, &line[line.len()..], "Expected ']'"));
+    }
+    Ok(entry_stack.pop().unwrap())
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    // Every packet in order. Every 2 packets is a pair.
+    type Input = Vec<Entry>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // Every 2 lines is a pair, with a blank line in between
+        let mut all_entries = vec![];
+
+        for line in input.lines() {
+            if line.is_empty() {
+                continue;
+            }
+
+            all_entries.push(parse_entry(line).map_err(|err| err.within(input, line))?);
+        }
+        Ok(all_entries)
+    }
+
+    fn part1(&self, all_entries: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        let mut pt1_result: usize = 0;
+        if !all_entries.len().is_multiple_of(2) {
+            return Err(Box::new(NoSolutionError(String::from(
+                "Packets should come in pairs",
+            ))));
+        }
+
+        for i in (0..all_entries.len()).step_by(2) {
+            let lhs = &all_entries[i];
+            let rhs = &all_entries[i + 1];
+            if lhs.cmp(rhs).is_le() {
+                let pair_idx = (i / 2) + 1;
+                pt1_result += pair_idx;
+            }
+        }
+        Ok(pt1_result)
+    }
+
+    fn part2(&self, all_entries: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        let mut all_entries = all_entries.clone();
+
+        // Add entries for 'divider packets'
+        let first_divider = Entry::List(vec![Entry::List(vec![Entry::Number(2)])]);
+        all_entries.push(first_divider.clone());
+        let second_divider = Entry::List(vec![Entry::List(vec![Entry::Number(6)])]);
+        all_entries.push(second_divider.clone());
+
+        all_entries.sort();
+
+        // Find the 2 divider packets. Start with the first, then use this to
+        // narrow the range to find the second.
+        let first_idx = all_entries.binary_search(&first_divider).unwrap() + 1;
+        let second_idx = &all_entries[first_idx..all_entries.len()]
+            .binary_search(&second_divider)
+            .unwrap()
+            + 1
+            + first_idx;
+
+        Ok(first_idx * second_idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n",
+            "[1,1,3,1,1]\n[1,1,5,1,1\n",
+            "[1,1,3,1,1]]\n[1,1,5,1,1]\n",
+            "[1,1,3,1,1]\n]\n",
+            "[1,1,3,1,1]\n[1,1[5],1,1]\n",
+            "[1,1,3,1,1]\n[1,1,99999999999,1,1]\n",
+            "[1,1,3,1,1]\n[1,1,x,1,1]\n",
+        ] {
+            assert!(solve_all(&Day13, input).is_err(), "{:?}", input);
+        }
+    }
+
+    // parse_entry used to unwrap the stack, and panic on anything it didn't
+    // expect. Add anything that fuzz/ finds here too, it just mustn't panic.
+    #[test]
+    fn fuzz_regressions() {
+        for input in ["]", "1", "[1[2]]", "[[", "99999999999", ",]", "[]]", "x"] {
+            let _ = parse_entry(input);
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::{cmp, error::Error};
+
+use aoc_common::{
+    parser::{
+        self, int, map, pair, preceded, separated1, spanned, tag, try_map, Failure, PResult, Parser,
+    },
+    visual::{self, Colour, Frame},
+    Direction, Grid, Solution, Vec2,
+};
+use tracing::{debug, trace};
+
+// The cave has y increasing downwards, so uses screen axes
+const DOWN: Vec2 = Direction::Down.screen();
+const LEFT: Vec2 = Direction::Left.screen();
+const RIGHT: Vec2 = Direction::Right.screen();
+
+// Mark the rock along `line` in the cave
+fn add_line(cave: &mut Grid<char>, line: &[Vec2]) {
+    // Start by marking the first coordinate alone,
+    // then iterate the remaining points.
+    let first = line.first();
+
+    // Shouldn't ever be the case but...
+    if let Some(first) = first {
+        cave[*first] = '#';
+    } else {
+        return;
+    }
+
+    for idx in 1..line.len() {
+        let begin = line[idx - 1];
+        let end = line[idx];
+
+        // Lines are always vertical or horizontal, which is checked
+        // when they're parsed.
+        let delta = (end - begin).signum();
+
+        let mut curr = begin;
+        while curr != end {
+            curr += delta;
+            cave[curr] = '#';
+        }
+    }
+}
+
+// Coordinates are kept small enough that the whole cave, floor and all, can
+// be simulated in one go.
+const MAX_COORD: i32 = 1000;
+
+// One coordinate of a point
+fn axis<'a>() -> impl Parser<'a, i32> {
+    try_map(int(), |value| {
+        if (0..=MAX_COORD).contains(&value) {
+            Ok(value)
+        } else {
+            Err(format!("Coordinate should be between 0 and {}", MAX_COORD))
+        }
+    })
+}
+
+fn point<'a>() -> impl Parser<'a, Vec2> {
+    map(pair(axis(), preceded(tag(","), axis())), |(x, y)| {
+        Vec2::new(x, y)
+    })
+}
+
+// The points along a line of rock, like 498,4 -> 498,6 -> 496,6. Rock only
+// goes horizontally or vertically between them.
+fn rock_line(s: &str) -> PResult<'_, Vec<Vec2>> {
+    let (points, rest) = separated1(spanned(point()), tag(" -> ")).parse(s)?;
+    for pair in points.windows(2) {
+        let ((prev, _), (point, text)) = (pair[0], pair[1]);
+        if point.x != prev.x && point.y != prev.y {
+            return Err(Failure::new(
+                text,
+                "Rock should be in horizontal or vertical lines",
+            ));
+        }
+    }
+    Ok((points.into_iter().map(|(point, _)| point).collect(), rest))
+}
+
+// Sand pours in from this point
+const SPAWN: Vec2 = Vec2 { x: 500, y: 0 };
+
+// Options for the shape of the cave. Part 1 has a bottomless cave, part 2
+// has an infinite floor two below the lowest rock.
+#[derive(Clone, Copy, Debug)]
+pub struct Cave {
+    pub floor: bool,
+}
+
+// A picture of the cave with sand in yellow
+fn draw(cave: &Grid<char>, settled_count: usize) -> Frame {
+    let caption = format!("{} units of sand at rest", settled_count);
+    Frame::grid(&caption, cave, |&c| {
+        let colour = match c {
+            '.' => Colour::Grey,
+            'o' => Colour::Yellow,
+            _ => Colour::Default,
+        };
+        (c, colour)
+    })
+}
+
+// Pour sand into the cave formed by the rock `lines` until it either falls
+// into the void or, if the cave has a floor, covers the spawn point. Returns
+// the number of units of sand that came to rest.
+pub fn pour_sand(lines: &[Vec<Vec2>], cave: &Cave) -> usize {
+    // Figure out the bounds of our terrain
+    let mut min = Vec2 {
+        x: i32::MAX,
+        y: i32::MAX,
+    };
+    let mut max = Vec2 {
+        x: i32::MIN,
+        y: i32::MIN,
+    };
+    for line in lines {
+        for point in line {
+            min.x = cmp::min(min.x, point.x);
+            min.y = cmp::min(min.y, point.y);
+            max.x = cmp::max(max.x, point.x);
+            max.y = cmp::max(max.y, point.y);
+        }
+    }
+
+    // Note that sand will fall in from SPAWN so we must
+    // include this in our bounds.
+    min.x = cmp::min(min.x, SPAWN.x);
+    min.y = cmp::min(min.y, SPAWN.y);
+    max.x = cmp::max(max.x, SPAWN.x);
+    max.y = cmp::max(max.y, SPAWN.y);
+
+    if cave.floor {
+        // Also, if there's a floor, consider that there is an infinite horizontal plane at max.y + 2.
+        // The condition to finish is that the spawn point is covered. We know that in the worst case
+        // this will happen when there is a pyramid formed by sand with its peak at the spawn point.
+        // For this we need horizontal bounds to be wide enough to capture this pyramid.
+        max.y += 2;
+
+        min.x = cmp::min(min.x, SPAWN.x - max.y - 1);
+        max.x = cmp::max(max.x, SPAWN.x + max.y + 1);
+    }
+
+    max.x += 1;
+    max.y += 1;
+
+    // Setup the sim. It does not necessarily start at coordinates (0,0), so
+    // offset it to cover just the area the sand can reach.
+    let mut sim = Grid::with_offset(max - min, min, '.');
+
+    for line in lines {
+        add_line(&mut sim, line);
+    }
+
+    if cave.floor {
+        // Add the floor as a line along the bottom
+        add_line(
+            &mut sim,
+            &[
+                Vec2 {
+                    x: min.x,
+                    y: max.y - 1,
+                },
+                Vec2 {
+                    x: max.x - 1,
+                    y: max.y - 1,
+                },
+            ],
+        );
+    }
+
+    // There are 2 states to the simulation, when we're dropping a
+    // block of sand in, it is the only thing that moves until it
+    // either settles or falls off the edge.
+    let mut settled_count = 0;
+    'outer: loop {
+        // Nothing more can get in once the spawn point is covered
+        if sim.get(SPAWN) != Some(&'.') {
+            break;
+        }
+
+        let mut next_coord = SPAWN;
+        sim[next_coord] = 'o';
+
+        // Iterate until at rest or off-screen
+        loop {
+            // Try immediately below
+            let immediately_below = next_coord + DOWN;
+            if let Some(&content) = sim.get(immediately_below) {
+                if content == '.' {
+                    sim[next_coord] = '.';
+                    next_coord = immediately_below;
+                    sim[next_coord] = 'o';
+                    continue;
+                }
+            } else {
+                break 'outer;
+            }
+            // Try below and to the left
+            let to_the_left = next_coord + DOWN + LEFT;
+            if let Some(&content) = sim.get(to_the_left) {
+                if content == '.' {
+                    sim[next_coord] = '.';
+                    next_coord = to_the_left;
+                    sim[next_coord] = 'o';
+                    continue;
+                }
+            } else {
+                break 'outer;
+            }
+
+            // Try below and to the right
+            let to_the_right = next_coord + DOWN + RIGHT;
+            if let Some(&content) = sim.get(to_the_right) {
+                if content == '.' {
+                    sim[next_coord] = '.';
+                    next_coord = to_the_right;
+                    sim[next_coord] = 'o';
+                    continue;
+                }
+            } else {
+                break 'outer;
+            }
+
+            // If none of the above were air, the sand comes to rest.
+            settled_count += 1;
+            visual::frame(|| draw(&sim, settled_count));
+            break;
+        }
+    }
+
+    debug!(
+        offset = %sim.offset(),
+        settled_count,
+        "Final grid:\n{}",
+        &sim
+    );
+
+    settled_count
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    // The points along each line of rock
+    type Input = Vec<Vec<Vec2>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let mut lines = vec![];
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            lines.push(parser::parse_all(input, line, &rock_line)?);
+        }
+
+        for line in &lines {
+            trace!(?line, "Parsed a line of rock");
+        }
+
+        Ok(lines)
+    }
+
+    // In part 1 sand eventually falls into the void
+    fn part1(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(pour_sand(input, &Cave { floor: false }))
+    }
+
+    // In part 2 there's a floor, so sand piles up until it covers the spawn
+    fn part2(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(pour_sand(input, &Cave { floor: true }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "498,4 -> 498,6 -> 496,6\n503\n",
+            "498,4 -> 498,6 => 496,6\n",
+            "498,4 -> 498,6 -> 496,7\n",
+            "498,4 -> 498,-6\n",
+            "498,4 -> 498,99999999\n",
+            "498,4 -> 498,6 ->\n",
+        ] {
+            assert!(solve_all(&Day14, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn stops_when_the_spawn_is_covered() {
+        // A closed bowl around the spawn point fills up without anything
+        // falling out of it.
+        let input = "498,0 -> 498,2 -> 502,2 -> 502,0\n";
+        assert_eq!(solve_all(&Day14, input).unwrap(), ["4", "4"]);
+        assert_eq!(solve_all(&Day14, "500,0 -> 501,0\n").unwrap(), ["0", "0"]);
+    }
+}
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use aoc_common::{solution::UnimplementedPartError, Part, Solution};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    // One string per line of the input, until there's something better
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    // Add each part here once it's solved, so that nothing runs or submits it
+    // before then
+    const PARTS: &'static [Part] = &[];
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, _input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Err(Box::new(UnimplementedPartError(Part::One)))
+    }
+
+    fn part2(&self, _input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Err(Box::new(UnimplementedPartError(Part::Two)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn solves_example() {
+        // Add the example's answers from the puzzle as each part is solved
+        let answers = solve_all(&Day15, include_str!("example.txt")).unwrap();
+        assert_eq!(answers, Vec::<String>::new());
+    }
+}
@@ -0,0 +1,453 @@
+use std::{error::Error, fmt};
+
+use aoc_common::{ParseError, Solution};
+
+// One of the game's moves, as its place in the list of moves
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Choice(usize);
+
+impl Choice {
+    // Later moves are worth more
+    fn points(&self) -> i32 {
+        self.0 as i32 + 1
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Res {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Res {
+    fn points(&self) -> i32 {
+        match self {
+            Res::Win => 6,
+            Res::Draw => 3,
+            Res::Loss => 0,
+        }
+    }
+}
+
+/// Which move to make in part 2 when a few of them get the result we want.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Prefer {
+    /// The first one in the list of moves
+    #[default]
+    First,
+    /// The one worth the most points, which is the last in the list
+    MostPoints,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameError(String);
+
+impl Error for GameError {}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bad game: {}", self.0)
+    }
+}
+
+// A game like rock paper scissors, where each player picks a move and whoever
+// picked the move that beats the other's wins. There can be any number of
+// moves, as long as there's an odd number of them and each one beats half of
+// the others, so whatever they play there's a move to lose, draw or win with.
+//
+// In part 1 of the strategy guide their moves are A, B, C, ... in order, and
+// ours are the same number of letters ending with Z, so rock paper scissors
+// has the puzzle's X, Y and Z. In part 2 the second column is always X, Y or
+// Z for lose, draw or win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<String>,
+    // beats[a][b] if move a beats move b
+    beats: Vec<Vec<bool>>,
+    prefer: Prefer,
+}
+
+impl Game {
+    // Any more than 25 and we'd run out of letters, as 26 is even
+    const MIN_MOVES: usize = 3;
+    const MAX_MOVES: usize = 25;
+
+    /// A game with `moves`, worth 1, 2, 3, ... points, where the first move
+    /// of each pair in `beats` beats the second.
+    pub fn new(
+        moves: Vec<String>,
+        beats: &[(String, String)],
+        prefer: Prefer,
+    ) -> Result<Game, GameError> {
+        if !(Self::MIN_MOVES..=Self::MAX_MOVES).contains(&moves.len()) {
+            return Err(GameError(format!(
+                "There are {} moves, but there need to be between {} and {}",
+                moves.len(),
+                Self::MIN_MOVES,
+                Self::MAX_MOVES
+            )));
+        }
+        if moves.len().is_multiple_of(2) {
+            return Err(GameError(format!(
+                "There are {} moves, but there needs to be an odd number so each \
+                 can beat half of the others",
+                moves.len()
+            )));
+        }
+        for (i, name) in moves.iter().enumerate() {
+            if moves[..i].contains(name) {
+                return Err(GameError(format!("'{}' is in the moves twice", name)));
+            }
+        }
+
+        let find = |name: &String| {
+            moves
+                .iter()
+                .position(|m| m == name)
+                .ok_or_else(|| GameError(format!("'{}' isn't one of the moves", name)))
+        };
+        let mut table = vec![vec![false; moves.len()]; moves.len()];
+        for (winner, loser) in beats {
+            let (w, l) = (find(winner)?, find(loser)?);
+            if w == l {
+                return Err(GameError(format!("'{}' can't beat itself", winner)));
+            }
+            table[w][l] = true;
+        }
+
+        // Every round needs exactly one winner, unless it's a draw
+        for a in 0..moves.len() {
+            for b in a + 1..moves.len() {
+                match (table[a][b], table[b][a]) {
+                    (true, true) => {
+                        return Err(GameError(format!(
+                            "'{}' and '{}' both beat each other",
+                            moves[a], moves[b]
+                        )))
+                    }
+                    (false, false) => {
+                        return Err(GameError(format!(
+                            "Nothing says whether '{}' or '{}' wins",
+                            moves[a], moves[b]
+                        )))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // Otherwise some move can't be beaten, or can't beat anything
+        let half = (moves.len() - 1) / 2;
+        for (name, row) in moves.iter().zip(&table) {
+            let wins = row.iter().filter(|&&beats| beats).count();
+            if wins != half {
+                return Err(GameError(format!(
+                    "'{}' beats {} moves, but each move needs to beat {}",
+                    name, wins, half
+                )));
+            }
+        }
+        Ok(Game {
+            moves,
+            beats: table,
+            prefer,
+        })
+    }
+
+    /// The game the puzzle plays.
+    pub fn rock_paper_scissors() -> Game {
+        let beats = [
+            ("Rock", "Scissors"),
+            ("Paper", "Rock"),
+            ("Scissors", "Paper"),
+        ];
+        Game::new(
+            ["Rock", "Paper", "Scissors"].map(String::from).to_vec(),
+            &beats.map(|(w, l)| (String::from(w), String::from(l))),
+            Prefer::First,
+        )
+        .expect("Rock paper scissors should be a fine game")
+    }
+
+    /// Read a game from TOML, with the moves in order and what each one
+    /// beats, e.g.
+    ///
+    /// ```toml
+    /// moves = ["Rock", "Paper", "Scissors"]
+    /// # Optional: "first" (the default) or "most-points"
+    /// prefer = "first"
+    ///
+    /// [beats]
+    /// Rock = ["Scissors"]
+    /// Paper = ["Rock"]
+    /// Scissors = ["Paper"]
+    /// ```
+    pub fn from_toml(s: &str) -> Result<Game, GameError> {
+        let table: toml::Table = s.parse().map_err(|err| GameError(format!("{}", err)))?;
+        let strings = |value: &toml::Value, what: &str| -> Result<Vec<String>, GameError> {
+            value
+                .as_array()
+                .and_then(|values| {
+                    values
+                        .iter()
+                        .map(|value| value.as_str().map(String::from))
+                        .collect()
+                })
+                .ok_or_else(|| GameError(format!("{} should be a list of strings", what)))
+        };
+
+        let moves = table
+            .get("moves")
+            .ok_or_else(|| GameError(String::from("There's no list of moves")))?;
+        let moves = strings(moves, "moves")?;
+        let beats = match table.get("beats") {
+            Some(toml::Value::Table(beats)) => beats,
+            _ => return Err(GameError(String::from("There's no [beats] table"))),
+        };
+        let mut pairs = vec![];
+        for (winner, losers) in beats {
+            for loser in strings(losers, &format!("What '{}' beats", winner))? {
+                pairs.push((winner.clone(), loser));
+            }
+        }
+        let prefer = match table.get("prefer").map(|value| value.as_str()) {
+            None => Prefer::First,
+            Some(Some("first")) => Prefer::First,
+            Some(Some("most-points")) => Prefer::MostPoints,
+            Some(_) => {
+                return Err(GameError(String::from(
+                    "prefer should be \"first\" or \"most-points\"",
+                )))
+            }
+        };
+        Game::new(moves, &pairs, prefer)
+    }
+
+    fn their_token(&self, i: usize) -> char {
+        (b'A' + i as u8) as char
+    }
+
+    fn my_token(&self, i: usize) -> char {
+        (b'Z' + 1 + i as u8 - self.moves.len() as u8) as char
+    }
+
+    // The place of `token` in the tokens made by `make`, if it's one of them
+    fn find_token<F: Fn(usize) -> char>(&self, token: &str, make: F) -> Option<usize> {
+        let mut chars = token.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+        (0..self.moves.len()).find(|&i| make(i) == c)
+    }
+
+    fn get_their_choice(&self, input: &str, token: &str) -> Result<Choice, ParseError> {
+        match self.find_token(token, |i| self.their_token(i)) {
+            Some(i) => Ok(Choice(i)),
+            None => Err(ParseError::new(
+                input,
+                token,
+                format!("Unexpected choice (theirs) '{}'", token),
+            )),
+        }
+    }
+
+    fn get_my_choice(&self, input: &str, token: &str) -> Result<Choice, ParseError> {
+        match self.find_token(token, |i| self.my_token(i)) {
+            Some(i) => Ok(Choice(i)),
+            None => Err(ParseError::new(
+                input,
+                token,
+                format!("Unexpected choice (mine) '{}'", token),
+            )),
+        }
+    }
+
+    fn get_desired_result(&self, input: &str, token: &str) -> Result<Res, ParseError> {
+        match token {
+            "X" => Ok(Res::Loss),
+            "Y" => Ok(Res::Draw),
+            "Z" => Ok(Res::Win),
+            _ => Err(ParseError::new(
+                input,
+                token,
+                format!("Unexpected result '{}'", token),
+            )),
+        }
+    }
+
+    // Read each line of the strategy guide as their choice and whatever
+    // `second` makes of the second column
+    fn parse_with<T, F>(&self, input: &str, second: F) -> Result<Vec<(Choice, T)>, ParseError>
+    where
+        F: Fn(&str) -> Result<T, ParseError>,
+    {
+        let mut rounds = vec![];
+        for entry in input.lines() {
+            let (theirs, column) = entry.split_once(' ').ok_or_else(|| {
+                ParseError::new(input, entry, "Expected two columns separated by a space")
+            })?;
+            rounds.push((self.get_their_choice(input, theirs)?, second(column)?));
+        }
+        Ok(rounds)
+    }
+
+    /// Read a strategy guide for part 1, where the second column is our move.
+    pub fn parse_moves(&self, input: &str) -> Result<Vec<(Choice, Choice)>, ParseError> {
+        self.parse_with(input, |token| self.get_my_choice(input, token))
+    }
+
+    /// Read a strategy guide for part 2, where the second column is the
+    /// result to get.
+    pub fn parse_results(&self, input: &str) -> Result<Vec<(Choice, Res)>, ParseError> {
+        self.parse_with(input, |token| self.get_desired_result(input, token))
+    }
+
+    fn get_result(&self, theirs: Choice, mine: Choice) -> Res {
+        if mine == theirs {
+            Res::Draw
+        } else if self.beats[mine.0][theirs.0] {
+            Res::Win
+        } else {
+            Res::Loss
+        }
+    }
+
+    // With more than three moves there are a few ways to win or lose, so the
+    // game says which to pick
+    fn get_my_choice_for_result(&self, theirs: Choice, res: Res) -> Choice {
+        let mut choices = (0..self.moves.len()).map(Choice);
+        let wanted = |&mine: &Choice| self.get_result(theirs, mine) == res;
+        match self.prefer {
+            Prefer::First => choices.find(wanted),
+            Prefer::MostPoints => choices.rev().find(wanted),
+        }
+        .expect("Each move beats and loses to some others")
+    }
+
+    /// The total score playing the moves in part 1's guide.
+    pub fn score_moves(&self, rounds: &[(Choice, Choice)]) -> i32 {
+        rounds
+            .iter()
+            .map(|&(theirs, mine)| mine.points() + self.get_result(theirs, mine).points())
+            .sum()
+    }
+
+    /// The total score getting the results in part 2's guide.
+    pub fn score_results(&self, rounds: &[(Choice, Res)]) -> i32 {
+        rounds
+            .iter()
+            .map(|&(theirs, res)| {
+                self.get_my_choice_for_result(theirs, res).points() + res.points()
+            })
+            .sum()
+    }
+}
+
+// The strategy guide read both ways, for rock paper scissors
+pub struct Guide {
+    game: Game,
+    moves: Vec<(Choice, Choice)>,
+    results: Vec<(Choice, Res)>,
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Guide;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let game = Game::rock_paper_scissors();
+        Ok(Guide {
+            moves: game.parse_moves(input)?,
+            results: game.parse_results(input)?,
+            game,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<i32, Box<dyn Error>> {
+        Ok(input.game.score_moves(&input.moves))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<i32, Box<dyn Error>> {
+        Ok(input.game.score_results(&input.results))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in ["A Y\nB", "A Y\nD X\n", "A W\n", "AX\n", "é Y\n", "A XY\n"] {
+            assert!(solve_all(&Day2, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn solves_example() {
+        assert_eq!(solve_all(&Day2, "A Y\nB X\nC Z\n").unwrap(), ["15", "12"]);
+    }
+
+    #[test]
+    fn reads_the_puzzles_game() {
+        let toml = "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n\n[beats]\nRock = [\"Scissors\"]\nPaper = [\"Rock\"]\nScissors = [\"Paper\"]\n";
+        assert_eq!(Game::from_toml(toml).unwrap(), Game::rock_paper_scissors());
+    }
+
+    #[test]
+    fn plays_rock_paper_scissors_lizard_spock() {
+        let game = Game::from_toml(include_str!("rpsls.toml")).unwrap();
+        // Spock (5) vaporizes rock for a win, then lizard (4) poisons Spock
+        let rounds = game.parse_moves("A Z\nE Y\n").unwrap();
+        assert_eq!(game.score_moves(&rounds), 5 + 6 + 4 + 6);
+        // V and W are only moves
+        let rounds = game.parse_moves("A V\n").unwrap();
+        assert_eq!(game.score_moves(&rounds), 1 + 3);
+        let err = game.parse_results("A Y\nA V\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn picks_the_move_the_game_prefers() {
+        let rpsls = include_str!("rpsls.toml");
+        // They play rock, and we want a draw then a win. Paper and Spock
+        // both beat rock, and paper comes first.
+        let game = Game::from_toml(rpsls).unwrap();
+        let rounds = game.parse_results("A Y\nA Z\n").unwrap();
+        assert_eq!(game.score_results(&rounds), 1 + 3 + 2 + 6);
+        // Spock is worth more
+        let game = Game::from_toml(&rpsls.replace("\"first\"", "\"most-points\"")).unwrap();
+        assert_eq!(game.score_results(&rounds), 1 + 3 + 5 + 6);
+    }
+
+    #[test]
+    fn broken_games_are_an_error() {
+        let games = [
+            // Too few moves
+            "moves = [\"Rock\", \"Paper\"]\n[beats]\nRock = [\"Paper\"]\n",
+            // Nobody wins paper against scissors
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n[beats]\nRock = [\"Scissors\"]\nPaper = [\"Rock\"]\n",
+            // Both win
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n[beats]\nRock = [\"Scissors\", \"Paper\"]\nPaper = [\"Rock\"]\nScissors = [\"Paper\"]\n",
+            // An even number of moves can't be fair
+            "moves = [\"A\", \"B\", \"C\", \"D\"]\n[beats]\nA = [\"B\", \"C\"]\nB = [\"C\", \"D\"]\nC = [\"D\"]\nD = [\"A\"]\n",
+            // Rock beats everything, and Spock nothing
+            "moves = [\"Rock\", \"Paper\", \"Scissors\", \"Lizard\", \"Spock\"]\n[beats]\nRock = [\"Paper\", \"Scissors\", \"Lizard\", \"Spock\"]\nPaper = [\"Scissors\", \"Lizard\", \"Spock\"]\nScissors = [\"Lizard\", \"Spock\"]\nLizard = [\"Spock\"]\n",
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\nprefer = \"last\"\n[beats]\nRock = [\"Scissors\"]\nPaper = [\"Rock\"]\nScissors = [\"Paper\"]\n",
+            "moves = [\"Rock\", \"Paper\", \"Rock\"]\n[beats]\n",
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n[beats]\nRock = [\"Rock\"]\n",
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n[beats]\nRock = [\"Well\"]\n",
+            "moves = [\"Rock\", \"Paper\", \"Scissors\"]\n",
+            "moves = \"Rock\"\n[beats]\n",
+            "moves = [",
+        ];
+        for game in games {
+            assert!(Game::from_toml(game).is_err(), "{:?}", game);
+        }
+    }
+}
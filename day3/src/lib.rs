@@ -0,0 +1,200 @@
+use std::error::Error;
+
+use aoc_common::{NoSolutionError, ParseError, Solution};
+
+// The priority of an item, if it's a letter
+fn to_priority(c: u8) -> Option<u8> {
+    if c.is_ascii_lowercase() {
+        Some(c - b'a' + 1)
+    } else if c.is_ascii_uppercase() {
+        Some(c - b'A' + 27)
+    } else {
+        None
+    }
+}
+
+// A bit for each item's priority, or None if any of them isn't a letter
+fn get_item_mask(s: &str) -> Option<u64> {
+    let mut mask: u64 = 0;
+    for c in s.bytes() {
+        mask |= 1u64 << to_priority(c)?;
+    }
+    Some(mask)
+}
+
+// The items in each compartment of a rucksack, as masks of their priorities
+pub struct Rucksack {
+    items: String,
+    lhs: u64,
+    rhs: u64,
+}
+
+impl Rucksack {
+    /// A rucksack holding `s`, or None if it isn't all letters or can't be
+    /// split into two equal compartments.
+    pub fn new(s: &str) -> Option<Rucksack> {
+        if !s.len().is_multiple_of(2) {
+            return None;
+        }
+        let (lhs, rhs) = s.split_at(s.len() / 2);
+        Some(Rucksack {
+            items: String::from(s),
+            lhs: get_item_mask(lhs)?,
+            rhs: get_item_mask(rhs)?,
+        })
+    }
+
+    fn get_item_mask(&self) -> u64 {
+        self.lhs | self.rhs
+    }
+
+    // The priority of an item in both compartments
+    fn find_duplicate(&self) -> Option<u8> {
+        let both = self.lhs & self.rhs;
+        (both != 0).then(|| both.trailing_zeros() as u8)
+    }
+}
+
+const ELVES_IN_GROUP: usize = 3;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    // One line per rucksack
+    type Input = Vec<Rucksack>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let mut sacks = vec![];
+        for line in input.lines() {
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(Box::new(ParseError::new(
+                    input,
+                    &line[idx..idx + c.len_utf8()],
+                    format!("Unexpected rucksack content '{}'", c),
+                )));
+            }
+            // Every item is a letter, so this is only for an odd number
+            let sack = Rucksack::new(line).ok_or_else(|| {
+                ParseError::new(
+                    input,
+                    line,
+                    "Rucksack can't be split into two equal compartments",
+                )
+            })?;
+            sacks.push(sack);
+        }
+        Ok(sacks)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<u64, Box<dyn Error>> {
+        let mut total_dup_prios: u64 = 0;
+        for sack in input {
+            let dup = sack.find_duplicate().ok_or_else(|| {
+                NoSolutionError(format!("No item in both compartments of '{}'", sack.items))
+            })?;
+            let dup_prio: u64 = dup.into();
+            total_dup_prios += dup_prio;
+        }
+        Ok(total_dup_prios)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<u64, Box<dyn Error>> {
+        let mut total_badge_prios: u64 = 0;
+        let mut group_count = 0;
+        let mut curr_badge_mask = !0u64;
+        for sack in input {
+            curr_badge_mask &= sack.get_item_mask();
+            group_count = (group_count + 1) % ELVES_IN_GROUP;
+            if group_count == 0 {
+                if curr_badge_mask == 0 {
+                    return Err(Box::new(NoSolutionError(String::from(
+                        "No badge common to a group of elves",
+                    ))));
+                }
+                let badge_prio: u64 = curr_badge_mask.trailing_zeros().into();
+                total_badge_prios += badge_prio;
+                curr_badge_mask = !0u64;
+            }
+        }
+        if group_count != 0 {
+            return Err(Box::new(NoSolutionError(format!(
+                "Number of elves isn't a multiple of {}",
+                ELVES_IN_GROUP
+            ))));
+        }
+        Ok(total_badge_prios)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use aoc_common::solution::solve_all;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "vJrwpWtwJgWrhcsFMMfFFhFp\nabc\n",
+            "vJrwpWtwJgWr hcsFMMfFFhFp\n",
+            "vJrwpWtwJgWrhcsFMMfFFhFé\n",
+            "abcd\n",
+            "aa\nbb\ncc\n",
+            "aa\naa\n",
+        ] {
+            assert!(solve_all(&Day3, input).is_err(), "{:?}", input);
+        }
+    }
+
+    fn items(s: &str) -> HashSet<u8> {
+        s.bytes().map(|c| to_priority(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn only_letters_in_equal_compartments_make_a_rucksack() {
+        assert!(Rucksack::new("abcd").is_some());
+        for items in ["abc", "ab1d", "abcé"] {
+            assert!(Rucksack::new(items).is_none(), "{:?}", items);
+        }
+    }
+
+    // A rucksack with two compartments of the same size
+    fn rucksack() -> impl Strategy<Value = String> {
+        (1..20usize)
+            .prop_flat_map(|len| prop::collection::vec("[a-zA-Z]", len * 2))
+            .prop_map(|items| items.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn duplicate_matches_set_intersection(s in rucksack()) {
+            let (lhs, rhs) = s.split_at(s.len() / 2);
+            let both: HashSet<u8> = items(lhs).intersection(&items(rhs)).copied().collect();
+            match Rucksack::new(&s).unwrap().find_duplicate() {
+                Some(dup) => prop_assert!(both.contains(&dup)),
+                None => prop_assert!(both.is_empty()),
+            }
+        }
+
+        #[test]
+        fn badge_mask_matches_set_intersection(group in prop::collection::vec(rucksack(), 3)) {
+            let mask = group
+                .iter()
+                .fold(!0u64, |mask, s| mask & Rucksack::new(s).unwrap().get_item_mask());
+            let common = group
+                .iter()
+                .map(|s| items(s))
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap();
+            let expected = common
+                .iter()
+                .fold(0u64, |mask, &prio| mask | 1u64 << prio);
+            prop_assert_eq!(mask, expected);
+        }
+    }
+}
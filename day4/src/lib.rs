@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::ops::Range;
+
+use aoc_common::{
+    parser::{self, pair, preceded, tag, try_map, uint, Parser},
+    Solution,
+};
+
+// A range of sections like 2-4, which includes both ends
+fn range<'a>() -> impl Parser<'a, Range<u32>> {
+    try_map(
+        pair(uint::<u32>(), preceded(tag("-"), uint::<u32>())),
+        |(start, upper)| match upper.checked_add(1) {
+            Some(end) => Ok(start..end),
+            None => Err(String::from("Range is too big")),
+        },
+    )
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    // The pair of section assignments on each line
+    type Input = Vec<(Range<u32>, Range<u32>)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parser::lines(
+            input,
+            &pair(range(), preceded(tag(","), range())),
+        )?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|(lhs, rhs)| {
+                (lhs.start <= rhs.start && lhs.end >= rhs.end)
+                    || (rhs.start <= lhs.start && rhs.end >= lhs.end)
+            })
+            .count())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|(lhs, rhs)| lhs.start < rhs.end && lhs.end > rhs.start)
+            .count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "2-4,6-8\n2-3",
+            "2-4\n",
+            "2-4,68\n",
+            "2-a,6-8\n",
+            "0-4294967295,1-2\n",
+        ] {
+            assert!(solve_all(&Day4, input).is_err(), "{:?}", input);
+        }
+    }
+}
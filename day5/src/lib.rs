@@ -0,0 +1,358 @@
+use std::{error::Error, fmt, iter::Iterator};
+
+use aoc_common::{
+    error::parse_in,
+    visual::{self, Colour, Frame},
+    NoSolutionError, ParseError, Solution,
+};
+use regex::Regex;
+use tracing::{debug, trace};
+
+#[derive(Clone)]
+pub struct Stacks(Vec<Vec<char>>);
+
+impl Stacks {
+    // Lifetime elision rule should be filling this out
+    // I wrote out lifetimes explicitly to satisfy myself that despite
+    // contained unsafe code this remains true.
+    #[allow(clippy::needless_lifetimes)]
+    fn get_mut_stack_pair<'a>(
+        &'a mut self,
+        (a, b): (usize, usize),
+    ) -> (&'a mut Vec<char>, &'a mut Vec<char>) {
+        // In order to get a mutable reference to source and dest
+        // we require some unsafe code. We know that source and dest
+        // never overlap and we panic if they are at runtime for some
+        // unexpected reason.
+        assert!(a != b);
+        unsafe {
+            // To explain this bit of magic for myself,
+            // each of these lines first takes a mutable reference to
+            // the sub-element we want, then casts it to a pointer (unsafe)
+            // then casts it back to a mutable reference. This
+            // avoids the borrow checker acting for the input &mut self
+            // and instead we get 2 new &mut Vec<char> that themselves
+            // have borrow checking.
+            (
+                &mut *(&mut self.0[a] as *mut _),
+                &mut *(&mut self.0[b] as *mut _),
+            )
+        }
+    }
+
+    /// Move crates from one stack to another.
+    /// This has the 'part 2' behaviour that when > 1 are
+    /// moved at a time, their relative ordering is maintained
+    /// in the move. The 'part 1' behaviour can be implemented on
+    /// top of this by only moving one crate at a time.
+    fn mov(&mut self, count: usize, from: usize, to: usize) -> Result<(), NoSolutionError> {
+        assert!(from < self.0.len() && to < self.0.len());
+        if self.0[from].len() < count {
+            return Err(NoSolutionError(format!(
+                "Can't move {} crates from column {} which only has {}",
+                count,
+                from + 1,
+                self.0[from].len()
+            )));
+        }
+        // Moving crates onto the stack they came from leaves it as it was
+        if from == to {
+            return Ok(());
+        }
+        let (from_stack, to_stack) = self.get_mut_stack_pair((from, to));
+        let crates = from_stack.drain(from_stack.len() - count..);
+        to_stack.extend(crates);
+        Ok(())
+    }
+}
+
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_columns = self.0.len();
+        // Find max stack height
+        let max_stack_height = self.0.iter().map(|x| x.len()).max().unwrap_or(0);
+        writeln!(
+            f,
+            "Stack with max height {max_stack_height} and {num_columns} columns"
+        )?;
+
+        // Write layer by layer the result, in the same style as the input
+        for y in (0..max_stack_height).rev() {
+            let mut first = true;
+            for column in &self.0 {
+                if first {
+                    write!(f, " ")?;
+                }
+                if column.len() > y {
+                    write!(f, "[{}]", column[y])?;
+                } else {
+                    write!(f, "   ")?;
+                }
+                first = false;
+            }
+            // Next line
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse the drawing of the stacks, up to and including the line numbering
+// them. The lines should come from `input`, which is used to say where any
+// errors are. Public so that it can be fuzzed, see fuzz/.
+pub fn parse_stacks<'a, I>(input: &str, lines: &mut I) -> Result<Stacks, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut contents: Vec<Vec<char>> = Vec::new();
+    let mut found_numbering = false;
+    'outer: for line in lines {
+        // Everything in the drawing is ascii, which means we can slice
+        // it up by bytes below
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(ParseError::new(
+                input,
+                &line[idx..idx + c.len_utf8()],
+                format!("Unexpected character '{}' in stacks", c),
+            ));
+        }
+
+        let mut remainder: &str = line;
+        let mut column = 0;
+        while !remainder.is_empty() {
+            // Expect a single space between columns, trim this off if not the first column
+            if column != 0 {
+                if !remainder.starts_with(' ') {
+                    return Err(ParseError::new(
+                        input,
+                        &remainder[..1],
+                        "Expected a space between stacks",
+                    ));
+                }
+                remainder = &remainder[1..];
+            }
+            trace!(remainder, "Chopped off the previous column");
+
+            if remainder.len() < 3 {
+                return Err(ParseError::new(
+                    input,
+                    remainder,
+                    "Expected a crate like '[A]', or three spaces",
+                ));
+            }
+            let (lhs, rhs) = remainder.split_at(3);
+
+            // Insert columns into the contents vector as we first see them
+            if column == contents.len() {
+                contents.push(Vec::<char>::new());
+            }
+
+            let chars: Vec<char> = lhs.chars().collect();
+            trace!(column, first = %chars[0], "Read a column");
+            // Push the contents
+            if chars[0] == '[' && chars[2] == ']' {
+                contents[column].push(chars[1]);
+            } else if chars[0] == ' ' && chars[2] == ' ' {
+                trace!(column, second = %chars[1], "Column has no crate");
+
+                // Check for column numbering
+                if chars[1].is_ascii_digit() {
+                    // End iteration over lines.
+                    // Next line should be instructions (ignoring blank lines)
+                    found_numbering = true;
+                    break 'outer;
+                } else if chars[1] != ' ' {
+                    return Err(ParseError::new(
+                        input,
+                        lhs,
+                        "Expected a stack number, or three spaces",
+                    ));
+                }
+            } else {
+                return Err(ParseError::new(
+                    input,
+                    lhs,
+                    "Expected a crate like '[A]', or three spaces",
+                ));
+            }
+
+            column += 1;
+            remainder = rhs;
+        }
+    }
+    if !found_numbering {
+        return Err(ParseError::new(
+            input,
+            &input[input.len()..],
+            "Expected a line numbering the stacks",
+        ));
+    }
+
+    // When we are done with this, all the stacks will be upside down
+    // because we parsed them that way. Reverse them now.
+    for column in &mut contents {
+        column.reverse();
+    }
+    Ok(Stacks(contents))
+}
+
+struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+pub struct Procedure {
+    stacks: Stacks,
+    moves: Vec<Move>,
+}
+
+// A picture of the stacks with the crates picked out
+fn draw(caption: &str, stacks: &Stacks) -> Frame {
+    Frame::text(caption, &stacks.to_string(), |c| match c {
+        '[' | ']' => Colour::Grey,
+        c if c.is_ascii_uppercase() => Colour::Yellow,
+        _ => Colour::Default,
+    })
+}
+
+// Apply all the moves to a copy of the initial stacks, using `mov` to
+// move the crates for each instruction, and read off the top crates.
+fn rearrange<F>(procedure: &Procedure, mov: F) -> Result<String, Box<dyn Error>>
+where
+    F: Fn(&mut Stacks, &Move) -> Result<(), NoSolutionError>,
+{
+    let mut stacks = procedure.stacks.clone();
+    visual::frame(|| draw("Initial stacks", &stacks));
+    for m in &procedure.moves {
+        trace!(
+            count = m.count,
+            from = m.from + 1,
+            to = m.to + 1,
+            "Moving crates"
+        );
+        mov(&mut stacks, m)?;
+        visual::frame(|| {
+            let caption = format!("move {} from {} to {}", m.count, m.from + 1, m.to + 1);
+            draw(&caption, &stacks)
+        });
+    }
+
+    debug!("Final stacks are:\n{}", stacks);
+
+    Ok(stacks
+        .0
+        .into_iter()
+        .filter_map(|x| x.last().copied())
+        .collect())
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Procedure;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let mut lines = input.lines();
+
+        // First parse the initial state of the stacks
+        let stacks = parse_stacks(input, &mut lines)?;
+
+        debug!("Initial stacks are:\n{}", stacks);
+
+        // Then the instructions
+        let re = Regex::new(r"^move (\d+) from (\d{1}) to (\d{1})$").unwrap();
+        let mut moves = vec![];
+        for line in lines {
+            if line.is_empty() {
+                continue;
+            }
+            let captures = re.captures(line).ok_or_else(|| {
+                ParseError::new(input, line, "Expected a move like 'move 1 from 2 to 3'")
+            })?;
+            let count: usize = parse_in(input, captures.get(1).unwrap().as_str())?;
+            // Stacks are numbered from 1 in the input
+            let column = |idx| {
+                let s = captures.get(idx).unwrap().as_str();
+                match parse_in::<usize>(input, s)? {
+                    n if n >= 1 && n <= stacks.0.len() => Ok(n - 1),
+                    _ => Err(ParseError::new(
+                        input,
+                        s,
+                        format!("There is no stack {}", s),
+                    )),
+                }
+            };
+            let from = column(2)?;
+            let to = column(3)?;
+            moves.push(Move { count, from, to });
+        }
+        Ok(Procedure { stacks, moves })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        // The CrateMover 9000 moves one crate at a time
+        rearrange(input, |stacks, m| {
+            for _ in 0..m.count {
+                stacks.mov(1, m.from, m.to)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        rearrange(input, |stacks, m| stacks.mov(m.count, m.from, m.to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    const STACKS: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+
+    #[test]
+    fn broken_input_is_an_error() {
+        let broken_moves = [
+            "move 1 from 2 to 1\nmove 3 from",
+            "move 1 from 4 to 1\n",
+            "move 1 from 0 to 1\n",
+            "move 9 from 1 to 2\n",
+            "move 99999999999999999999 from 1 to 2\n",
+        ];
+        for moves in broken_moves {
+            let input = format!("{}{}", STACKS, moves);
+            assert!(solve_all(&Day5, &input).is_err(), "{:?}", input);
+        }
+
+        let broken_stacks = [
+            "[Z] [M] [P]\n",
+            "[Z] [M] [P\n 1   2   3 \n",
+            "[Z]-[M] [P]\n 1   2   3 \n",
+            "[Z] (M) [P]\n 1   2   3 \n",
+            "[Z] [é] [P]\n 1   2   3 \n",
+        ];
+        for input in broken_stacks {
+            assert!(solve_all(&Day5, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn moves_onto_same_stack() {
+        let input = format!("{}move 1 from 1 to 1\n", STACKS);
+        assert_eq!(solve_all(&Day5, &input).unwrap(), ["NDP", "NDP"]);
+    }
+
+    // parse_stacks used to slice lines by byte offset. Add anything that
+    // fuzz/ finds here too, it just mustn't panic.
+    #[test]
+    fn fuzz_regressions() {
+        for input in ["", "[", "[Z", " 1 ", "\u{e9}[Z]\n 1 ", "[Z]\n 1\n"] {
+            let _ = parse_stacks(input, &mut input.lines());
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::error::Error;
+
+use aoc_common::{NoSolutionError, ParseError, Solution};
+
+// Parsing checks that the input is all lowercase letters
+fn to_idx(c: u8) -> usize {
+    assert!(c.is_ascii_lowercase());
+    (c - b'a') as usize
+}
+
+// Find the number of characters that need processing before the first
+// window of `marker_len` distinct characters has been seen.
+fn find_marker(input: &[u8], marker_len: usize) -> Result<usize, Box<dyn Error>> {
+    // The method is very simple. We assume the window length is not too long
+    // and we just build a bitset each time and count the bits in order to
+    // determine if we have found the marker
+    if input.len() < marker_len {
+        return Err(Box::new(NoSolutionError(format!(
+            "input is shorter than a {} character marker",
+            marker_len
+        ))));
+    }
+    assert!(input.len() >= marker_len);
+    let mut found_idx: Option<usize> = None;
+    'outer: for idx in 0..=input.len() - marker_len {
+        let slice = &input[idx..idx + marker_len];
+        let mut bits: u32 = 0;
+        for c in slice {
+            let prev = bits;
+            bits |= 1u32 << to_idx(*c);
+            if bits == prev {
+                // If bits didn't change, we know there were duplicate letters so break
+                continue 'outer;
+            }
+        }
+        found_idx = Some(idx);
+        break;
+    }
+    if let Some(idx) = found_idx {
+        Ok(idx + marker_len)
+    } else {
+        Err(Box::new(NoSolutionError(format!(
+            "no {} character marker in input",
+            marker_len
+        ))))
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // We have a single line input to parse.
+        let line = input.trim_end();
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(Box::new(ParseError::new(
+                input,
+                &line[idx..idx + c.len_utf8()],
+                format!(
+                    "Unexpected character '{}' in datastream",
+                    c.escape_default()
+                ),
+            )));
+        }
+        Ok(line.bytes().collect())
+    }
+
+    // Part 1 looks for the start-of-packet marker, part 2 the start-of-message
+    // marker. The only difference is the number of distinct characters.
+    fn part1(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        find_marker(input, 4)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        find_marker(input, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use aoc_common::solution::solve_all;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "",
+            "abc",
+            "aaaaaaaaaaaaaaaaaaaa",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb\nabc",
+            "mjqjPqmgbljsphdztnvjfqwrcgsmlb",
+        ] {
+            assert!(solve_all(&Day6, input).is_err(), "{:?}", input);
+        }
+    }
+
+    // Check every window in turn with a set, to compare the bitset against
+    fn find_marker_naive(input: &[u8], marker_len: usize) -> Option<usize> {
+        input
+            .windows(marker_len)
+            .position(|window| window.iter().collect::<HashSet<_>>().len() == marker_len)
+            .map(|idx| idx + marker_len)
+    }
+
+    proptest! {
+        #[test]
+        fn marker_matches_naive_search(
+            input in "[a-p]{0,40}",
+            marker_len in 1..16usize,
+        ) {
+            let input = input.as_bytes();
+            prop_assert_eq!(
+                find_marker(input, marker_len).ok(),
+                find_marker_naive(input, marker_len)
+            );
+        }
+    }
+}
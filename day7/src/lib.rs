@@ -0,0 +1,313 @@
+use std::{
+    cell::RefCell,
+    cmp::min,
+    error::Error,
+    fmt,
+    io::Write,
+    rc::{Rc, Weak},
+};
+
+use aoc_common::{error::parse_in, ParseError, Solution};
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy)]
+enum DirectoryTreeNodeType {
+    Directory,
+    File(usize), // Also has a size
+}
+
+#[derive(Debug, Clone)]
+pub struct DirectoryTree {
+    parent: Weak<RefCell<DirectoryTree>>,
+    children: Vec<Rc<RefCell<DirectoryTree>>>,
+    name: String,
+    node_type: DirectoryTreeNodeType,
+}
+
+impl DirectoryTree {
+    fn new(
+        name: String,
+        parent: Weak<RefCell<DirectoryTree>>,
+        node_type: DirectoryTreeNodeType,
+    ) -> Self {
+        Self {
+            parent,
+            children: vec![],
+            name,
+            node_type,
+        }
+    }
+
+    fn root() -> Self {
+        Self {
+            parent: Weak::new(),
+            children: vec![],
+            name: String::from(""),
+            node_type: DirectoryTreeNodeType::Directory,
+        }
+    }
+
+    fn path_name(&self) -> String {
+        let mut buf: Vec<u8> = Vec::new();
+        let mut next = self.parent.clone();
+        let mut path_stack = vec![];
+        while let Some(parent) = next.upgrade() {
+            path_stack.push(parent.clone());
+            next = parent.borrow().parent.clone();
+        }
+        for part in path_stack.into_iter().rev() {
+            write!(buf, "{}/", part.borrow().name).unwrap();
+        }
+        write!(buf, "{}", self.name).unwrap();
+        std::str::from_utf8(&buf[..]).unwrap().to_string()
+    }
+}
+
+impl fmt::Display for DirectoryTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.path_name())?;
+        if let DirectoryTreeNodeType::File(size) = self.node_type {
+            write!(f, " size {}", size)?;
+        }
+        writeln!(f)?;
+
+        // Print children recursively
+        for child in &self.children {
+            child.borrow().fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+// We will need some struct to track directory structure etc. Public so that
+// it can be fuzzed, see fuzz/.
+pub fn process_commands(input: &str) -> Result<Rc<RefCell<DirectoryTree>>, Box<dyn Error>> {
+    let root_node = Rc::new(RefCell::new(DirectoryTree::root()));
+
+    let mut curr_node = root_node.clone();
+    let mut ls = false;
+    for line in input.lines() {
+        if let Some(command) = line.strip_prefix("$ ") {
+            // Commands might not have an argument, in which case it's the
+            // empty string at the end of the line
+            let (cmd_token, arg) = command
+                .split_once(' ')
+                .unwrap_or((command, &command[command.len()..]));
+            match cmd_token {
+                "ls" => {
+                    ls = true;
+                }
+                "cd" => {
+                    curr_node = match arg {
+                        "/" => root_node.clone(),
+                        ".." => {
+                            // Get parent node
+                            if let Some(parent) = curr_node.borrow().parent.upgrade() {
+                                parent.clone()
+                            } else {
+                                return Err(Box::new(ParseError::new(
+                                    input,
+                                    arg,
+                                    "Current directory has no parent",
+                                )));
+                            }
+                        }
+                        _ => {
+                            if let Some(child) = curr_node
+                                .borrow()
+                                .children
+                                .iter()
+                                .find(|x| x.borrow().name == arg)
+                            {
+                                child.clone()
+                            } else {
+                                return Err(Box::new(ParseError::new(
+                                    input,
+                                    arg,
+                                    format!("Current directory has no child with name '{}'", arg),
+                                )));
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    return Err(Box::new(ParseError::new(
+                        input,
+                        cmd_token,
+                        format!("Unrecognised command '{}'", cmd_token),
+                    )))
+                }
+            }
+        } else {
+            if !ls {
+                return Err(Box::new(ParseError::new(
+                    input,
+                    line,
+                    "Line must start with '$ ' unless this line represents the output of an ls command",
+                )));
+            }
+
+            // Process output - it will either start with 'dir' and indicate a directory or with a number to indicate file size
+            if let Some(dirname) = line.strip_prefix("dir ") {
+                // Insert a new directory below the current node
+                curr_node
+                    .borrow_mut()
+                    .children
+                    .push(Rc::new(RefCell::new(DirectoryTree::new(
+                        String::from(dirname),
+                        Rc::downgrade(&curr_node),
+                        DirectoryTreeNodeType::Directory,
+                    ))));
+            } else {
+                if let Some((size, name)) = line.split_once(' ') {
+                    let size: usize = parse_in(input, size)?;
+                    curr_node
+                        .borrow_mut()
+                        .children
+                        .push(Rc::new(RefCell::new(DirectoryTree::new(
+                            String::from(name),
+                            Rc::downgrade(&curr_node),
+                            DirectoryTreeNodeType::File(size),
+                        ))))
+                } else {
+                    return Err(Box::new(ParseError::new(
+                        input,
+                        line,
+                        "Not a valid line of ls output",
+                    )));
+                }
+            }
+        }
+    }
+    Ok(root_node)
+}
+
+fn iterate_directory_sizes<F: FnMut(&Rc<RefCell<DirectoryTree>>, usize)>(
+    tree: &Rc<RefCell<DirectoryTree>>,
+    f: &mut F,
+) -> usize {
+    if let DirectoryTreeNodeType::File(size) = tree.borrow().node_type {
+        size
+    } else {
+        let total_size = tree
+            .borrow()
+            .children
+            .iter()
+            .map(|c| iterate_directory_sizes(c, f))
+            // Silly file sizes shouldn't be able to overflow the total
+            .fold(0, usize::saturating_add);
+        f(tree, total_size);
+        total_size
+    }
+}
+
+const SIZE_LIMIT: usize = 100000;
+
+// Part 2
+// Find the smallest directory that can be deleted that makes the total
+// filesystem usage less than or equal the target usage. The target usage
+// is that which leaves enough space for the 'update'
+const FILESYSTEM_SPACE: usize = 70000000;
+const UPDATE_SPACE_REQUIRED: usize = 30000000;
+const TARGET_FILESYSTEM_USAGE: usize = FILESYSTEM_SPACE - UPDATE_SPACE_REQUIRED;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Rc<RefCell<DirectoryTree>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // Process commands
+        let directory_tree = process_commands(input)?;
+
+        // Print out the directory tree just ot see if it looks correct
+        debug!("Directory tree:\n{}", directory_tree.borrow());
+
+        Ok(directory_tree)
+    }
+
+    fn part1(&self, directory_tree: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        // Using a DFS of directory tree we identify the size of each
+        // directory during iteration
+        let mut dirs_under_limit = vec![];
+        iterate_directory_sizes(directory_tree, &mut |dir, size| {
+            if size <= SIZE_LIMIT {
+                debug!(
+                    dir = %dir.borrow().path_name(),
+                    size,
+                    limit = SIZE_LIMIT,
+                    "Directory is under the limit"
+                );
+                dirs_under_limit.push((dir.clone(), size));
+            }
+        });
+
+        // Seems an ideal situation in which to implement an iterator for the directory sizes.
+        // However the nature of the DFS at the moment is such that maintaining the state required
+        // seems tricky unless we do it through a coroutine because of the state that needs
+        // maintaining in the iterator.
+        Ok(dirs_under_limit.iter().map(|e| e.1).sum())
+    }
+
+    fn part2(&self, directory_tree: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        let used_space = iterate_directory_sizes(directory_tree, &mut |_, _| {});
+        let min_space_to_free = used_space - min(used_space, TARGET_FILESYSTEM_USAGE);
+        let mut smallest = usize::MAX;
+        iterate_directory_sizes(directory_tree, &mut |_, size| {
+            if size >= min_space_to_free {
+                smallest = min(smallest, size);
+            }
+        });
+        Ok(smallest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "$ cd /\n$ ls\ndir a\n$ cd b\n",
+            "$ cd /\n$ cd ..\n",
+            "$ cd /\n$ rm -rf a\n",
+            "$ cd /\n$\n",
+            "$ cd /\ndir a\n",
+            "$ cd /\n$ ls\n12\n",
+            "$ cd /\n$ ls\nabc d.txt\n",
+            "$ cd /\n$ ls\né\n",
+            "$ cd\n",
+            "$",
+        ] {
+            assert!(solve_all(&Day7, input).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn huge_files_dont_overflow() {
+        let input = "$ cd /\n$ ls\n18446744073709551615 a\n18446744073709551615 b\n";
+        assert!(solve_all(&Day7, input).is_ok());
+    }
+
+    // Short lines used to be sliced past their end. Add anything that fuzz/
+    // finds here too, it just mustn't panic.
+    #[test]
+    fn fuzz_regressions() {
+        for input in [
+            "$",
+            "$ c",
+            "$ cd",
+            "d",
+            "dir",
+            "$ ls\n1",
+            "$ cd /\n$ cd ..\n",
+        ] {
+            let _ = process_commands(input);
+        }
+    }
+}
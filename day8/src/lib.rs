@@ -0,0 +1,191 @@
+use std::error::Error;
+
+use aoc_common::{Grid, Solution, Vec2};
+use tracing::debug;
+
+// Every line of the input is a row of digits, and they must all be the same
+// length.
+fn parse_trees(input: &str) -> Result<Grid<u32>, Box<dyn Error>> {
+    Ok(Grid::parse(input, |_, c| {
+        c.to_digit(10)
+            .ok_or_else(|| format!("Expected a tree height, got '{}'", c))
+    })?)
+}
+
+// Walk along a line of trees from the edge, marking each one that's taller
+// than every tree before it as visible. Returns how many weren't already
+// marked.
+fn mark_visible<'a, I>(line: I, visible: &mut Grid<bool>) -> usize
+where
+    I: Iterator<Item = (Vec2, &'a u32)>,
+{
+    let mut count = 0;
+    let mut curr_max: Option<u32> = None;
+    for (coord, &tree_size) in line {
+        if curr_max.is_none_or(|curr_max| tree_size > curr_max) {
+            if !std::mem::replace(&mut visible[coord], true) {
+                count += 1;
+            }
+            curr_max = Some(tree_size);
+        }
+    }
+    count
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let mat = parse_trees(input)?;
+
+        debug!("Initial matrix:\n{}", mat);
+
+        Ok(mat)
+    }
+
+    fn part1(&self, mat: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        let mut visible = Grid::new(mat.dims(), false);
+
+        // Look in from every side
+        let mut visible_count: usize = 0;
+        for y in 0..mat.height() {
+            visible_count += mark_visible(mat.row(y), &mut visible);
+            visible_count += mark_visible(mat.row(y).rev(), &mut visible);
+        }
+        for x in 0..mat.width() {
+            visible_count += mark_visible(mat.column(x), &mut visible);
+            visible_count += mark_visible(mat.column(x).rev(), &mut visible);
+        }
+
+        Ok(visible_count)
+    }
+
+    fn part2(&self, mat: &Self::Input) -> Result<u64, Box<dyn Error>> {
+        let mut best = Vec2::zero();
+        let mut best_score: Option<u64> = None;
+        for (coord, &height) in mat.iter() {
+            let Vec2 {
+                x: score_x,
+                y: score_y,
+            } = coord;
+            let at = |x, y| mat[Vec2 { x, y }];
+
+            // Check each direction
+            let max_x = ((score_x + 1)..mat.width())
+                .find(|&x| at(x, score_y) >= height)
+                .unwrap_or(mat.width() - 1);
+            let min_x = (0..score_x)
+                .rev()
+                .find(|&x| at(x, score_y) >= height)
+                .unwrap_or(0);
+            let max_y = ((score_y + 1)..mat.height())
+                .find(|&y| at(score_x, y) >= height)
+                .unwrap_or(mat.height() - 1);
+            let min_y = (0..score_y)
+                .rev()
+                .find(|&y| at(score_x, y) >= height)
+                .unwrap_or(0);
+
+            // Add up the score
+            let score = [
+                max_x - score_x,
+                score_x - min_x,
+                max_y - score_y,
+                score_y - min_y,
+            ]
+            .iter()
+            .map(|&distance| distance as u64)
+            .product();
+
+            if best_score.is_none_or(|best_score| score > best_score) {
+                best_score = Some(score);
+                best = coord;
+            }
+        }
+
+        // The grid always has at least one tree
+        let best_score = best_score.unwrap_or_default();
+        debug!(at = %best, score = best_score, "Best scenic score");
+
+        Ok(best_score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in [
+            "",
+            "\n",
+            "303\n25\n",
+            "303\n2a5\n",
+            "30373\n255\n12\n",
+            "3é\n12\n",
+        ] {
+            assert!(solve_all(&Day8, input).is_err(), "{:?}", input);
+        }
+    }
+
+    // The trees in each direction from (x, y), nearest first
+    fn lines_of_sight(rows: &[Vec<u32>], x: usize, y: usize) -> [Vec<u32>; 4] {
+        [
+            (0..x).rev().map(|x| rows[y][x]).collect(),
+            (x + 1..rows[y].len()).map(|x| rows[y][x]).collect(),
+            (0..y).rev().map(|y| rows[y][x]).collect(),
+            (y + 1..rows.len()).map(|y| rows[y][x]).collect(),
+        ]
+    }
+
+    // Look out from every tree in turn, O(n³) for an n by n forest
+    fn brute_force(rows: &[Vec<u32>]) -> (usize, u64) {
+        let mut visible = 0;
+        let mut best_score = 0;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &height) in row.iter().enumerate() {
+                let sights = lines_of_sight(rows, x, y);
+                if sights.iter().any(|line| line.iter().all(|&h| h < height)) {
+                    visible += 1;
+                }
+                let score: u64 = sights
+                    .iter()
+                    .map(|line| match line.iter().position(|&h| h >= height) {
+                        Some(idx) => idx as u64 + 1,
+                        None => line.len() as u64,
+                    })
+                    .product();
+                best_score = best_score.max(score);
+            }
+        }
+        (visible, best_score)
+    }
+
+    fn forest() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        (1..10usize, 1..10usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::collection::vec(0..10u32, width), height)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(rows in forest()) {
+            let input: String = rows
+                .iter()
+                .map(|row| row.iter().map(|h| h.to_string()).collect::<String>() + "\n")
+                .collect();
+            let trees = Solution::parse(&Day8, &input).unwrap();
+            let (visible, best_score) = brute_force(&rows);
+            prop_assert_eq!(Day8.part1(&trees).unwrap(), visible);
+            prop_assert_eq!(Day8.part2(&trees).unwrap(), best_score);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{collections::HashSet, error::Error};
+
+use aoc_common::{
+    parser::{self, one_of, pair, preceded, tag, uint},
+    visual::{self, Colour, Frame},
+    Direction, Grid, Solution, Vec2,
+};
+
+// How much of the rope's surroundings to draw, centred on the head
+const VIEW: Vec2 = Vec2::new(61, 21);
+
+// A picture of the rope around its head, with the places the tail has been.
+// The rope moves on maths axes, so is flipped over to draw it.
+fn draw(positions: &[Vec2], visited: &HashSet<Vec2>) -> Frame {
+    let flip = |p: Vec2| Vec2::new(p.x, -p.y);
+    let head = flip(positions[0]);
+    let mut view = Grid::with_offset(
+        VIEW,
+        head - Vec2::new(VIEW.x / 2, VIEW.y / 2),
+        ('.', Colour::Grey),
+    );
+    for &p in visited {
+        if view.contains(flip(p)) {
+            view[flip(p)] = ('#', Colour::Blue);
+        }
+    }
+    // Draw from the tail up, so knots further up the rope go on top
+    for (idx, &p) in positions.iter().enumerate().rev() {
+        let knot = match idx {
+            0 => ('H', Colour::Red),
+            _ if idx == positions.len() - 1 => ('T', Colour::Green),
+            _ => (
+                char::from_digit(idx as u32 % 10, 10).unwrap(),
+                Colour::Yellow,
+            ),
+        };
+        if view.contains(flip(p)) {
+            view[flip(p)] = knot;
+        }
+    }
+    let caption = format!(
+        "Head at {}, tail has visited {} places",
+        positions[0],
+        visited.len()
+    );
+    Frame::grid(&caption, &view, |&cell| cell)
+}
+
+// Count the positions visited by the tail of a rope with `num_nodes` knots.
+fn simulate(motions: &[(Direction, i32)], num_nodes: usize) -> usize {
+    // Initial state
+    // 'head' is the first in the list, tail nodes are higher indices
+    let mut positions = vec![Vec2::zero(); num_nodes];
+
+    let mut visited_positions = HashSet::new();
+    // Initial visited position - always the last tail node
+    visited_positions.insert(*positions.last().unwrap());
+
+    for (dir, num_steps) in motions {
+        // Which way up doesn't matter here, so long as it's consistent
+        let step_delta = dir.math();
+
+        for _ in 0..*num_steps {
+            let mut delta = step_delta;
+
+            for idx in 0..positions.len() - 1 {
+                positions[idx] += delta;
+
+                // The next node in the chain only moves if it's no longer
+                // touching this one
+                if positions[idx].chebyshev(positions[idx + 1]) > 1 {
+                    delta = (positions[idx] - positions[idx + 1]).signum();
+                } else {
+                    delta = Vec2::zero();
+                    // We can exit early here if we didn't move
+                    break;
+                }
+            }
+
+            // If the last tail node should move, move it and update visited positions
+            if delta != Vec2::zero() {
+                *positions.last_mut().unwrap() += delta;
+                visited_positions.insert(*positions.last().unwrap());
+            }
+            visual::frame(|| draw(&positions, &visited_positions));
+        }
+    }
+
+    visited_positions.len()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    // Direction and number of steps of each motion of the head
+    type Input = Vec<(Direction, i32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // Each input should consist of a direction followed by a number of
+        // steps in that direction
+        let direction = one_of(&[
+            ("U", Direction::Up),
+            ("D", Direction::Down),
+            ("L", Direction::Left),
+            ("R", Direction::Right),
+        ]);
+        Ok(parser::lines(
+            input,
+            &pair(direction, preceded(tag(" "), uint())),
+        )?)
+    }
+
+    // Part 1 simulates a rope with just a head and a tail
+    fn part1(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(simulate(input, 2))
+    }
+
+    // Part 2 simulates a rope with ten knots
+    fn part2(&self, input: &Self::Input) -> Result<usize, Box<dyn Error>> {
+        Ok(simulate(input, 10))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::solution::solve_all;
+
+    use super::*;
+
+    #[test]
+    fn broken_input_is_an_error() {
+        for input in ["R 4\nU", "R 4\nX 4\n", "R four\n", "R4\n", "é 4\n"] {
+            assert!(solve_all(&Day9, input).is_err(), "{:?}", input);
+        }
+    }
+}